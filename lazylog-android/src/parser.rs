@@ -1,5 +1,6 @@
+use chrono::{DateTime, Local, NaiveTime};
 use lazy_static::lazy_static;
-use lazylog_framework::provider::{LogDetailLevel, LogItem, LogParser};
+use lazylog_framework::provider::{LogDetailLevel, LogItem, LogParser, resolve_yearless_timestamp};
 use lazylog_parser::process_delta;
 use regex::Regex;
 
@@ -29,6 +30,17 @@ impl AndroidParser {
         }
         content.to_string()
     }
+
+    /// parse the `MM-DD HH:MM:SS.mmm` part of a `-v long` header line,
+    /// the missing year is resolved against `reference`
+    fn header_timestamp(raw_log: &str, reference: DateTime<Local>) -> Option<DateTime<Local>> {
+        let header = raw_log.lines().next()?.trim().strip_prefix('[')?;
+        let mut fields = header.split_whitespace();
+        let (month, day) = fields.next()?.split_once('-')?;
+        let time = NaiveTime::parse_from_str(fields.next()?, "%H:%M:%S%.f").ok()?;
+
+        resolve_yearless_timestamp(month.parse().ok()?, day.parse().ok()?, time, reference)
+    }
 }
 
 impl Default for AndroidParser {
@@ -90,11 +102,15 @@ impl LogParser for AndroidParser {
             String::new()
         };
 
-        // framework generates time automatically
-        let item = LogItem::new(message.clone(), raw_log.to_string())
-            .with_metadata("level", level.to_string())
+        let mut item = LogItem::new(message.clone(), raw_log.to_string())
+            .with_level(level)
             .with_metadata("tag", tag.to_string());
 
+        // fall back to the ingestion time generated by the framework
+        if let Some(timestamp) = Self::header_timestamp(raw_log, Local::now()) {
+            item = item.with_timestamp(timestamp);
+        }
+
         Some(item)
     }
 
@@ -209,7 +225,13 @@ impl LogParser for AndroidEffectParser {
         let log_items = process_delta(inner_content);
 
         // return first parsed item if available
-        if let Some(item) = log_items.into_iter().next() {
+        if let Some(mut item) = log_items.into_iter().next() {
+            // the logcat header has milliseconds, the structured `## date` only seconds;
+            // the latter still pins down the year the header lacks
+            let reference = item.timestamp.unwrap_or_else(Local::now);
+            if let Some(timestamp) = AndroidParser::header_timestamp(raw_log, reference) {
+                item = item.with_timestamp(timestamp);
+            }
             return Some(item);
         }
 
@@ -233,6 +255,7 @@ impl LogParser for AndroidEffectParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use lazylog_framework::provider::Severity;
 
     #[test]
     fn test_parse_android_log_with_empty_tag() {
@@ -260,6 +283,8 @@ mod tests {
         let item = result.unwrap();
         assert_eq!(item.get_metadata("level").unwrap(), "E");
         assert_eq!(item.get_metadata("tag").unwrap(), "[Effect]");
+        assert_eq!(item.severity, Some(Severity::Error));
+        assert_eq!(parser.severity(&item), Some(Severity::Error));
     }
 
     #[test]
@@ -333,6 +358,7 @@ Prepared frame frame 15."#;
 
         // verify all metadata fields are extracted from structured content
         assert_eq!(item.get_metadata("level"), Some("info"));
+        assert_eq!(item.severity, Some(Severity::Info));
         assert_eq!(
             item.get_metadata("origin"),
             Some("tid:30427,AMGText.cpp:885")
@@ -359,6 +385,7 @@ Prepared frame frame 15."#;
 
         let item = result.unwrap();
         assert_eq!(item.get_metadata("level"), Some("SYSTEM"));
+        assert_eq!(item.severity, Some(Severity::Notice));
         assert_eq!(
             item.get_metadata("origin"),
             Some("threadid:1215784752,ConsoleModule.cpp,103")
@@ -376,4 +403,33 @@ plain unstructured message"#;
         let result = parser.parse(raw_log);
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_parse_android_log_timestamp() {
+        let parser = AndroidParser::new();
+        let raw_log = r#"[ 11-14 14:50:22.618  3264: 3264 I/wificond ]
+station_bandwidth: "#;
+
+        let item = parser.parse(raw_log).unwrap();
+        let timestamp = item.timestamp.unwrap();
+        assert_eq!(
+            timestamp.format("%m-%d %H:%M:%S%.3f").to_string(),
+            "11-14 14:50:22.618"
+        );
+        assert_eq!(item.time, "14:50:22.618");
+    }
+
+    #[test]
+    fn test_effect_parser_uses_header_precision_and_structured_year() {
+        let parser = AndroidEffectParser::new();
+        let raw_log = r#"[ 11-14 15:48:35.131 20387:30427 I/[Effect] ]
+## 2025-11-14 15:48:35 [tid:30427,AMGText.cpp:885] info ## [AE_TEXT_TAG]Set Text bloom path: "#;
+
+        let item = parser.parse(raw_log).unwrap();
+        let timestamp = item.timestamp.unwrap();
+        assert_eq!(
+            timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            "2025-11-14 15:48:35.131"
+        );
+    }
 }
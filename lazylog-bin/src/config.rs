@@ -0,0 +1,467 @@
+use crate::ParserKind;
+use lazylog_framework::provider::LogDetailLevel;
+use lazylog_framework::{
+    Action, FilterContext, FilterMode, KeySequence, Keymap, OverflowPolicy, Theme,
+};
+use ratatui::style::Color;
+use serde::{
+    Deserialize, Deserializer,
+    de::{Error as _, MapAccess, Visitor},
+};
+use std::{env, fmt, fs, io, path::PathBuf, str::FromStr};
+
+/// highest detail level of any parser, lower parser maximums are applied by the app
+const MAX_DETAIL_LEVEL: LogDetailLevel = 4;
+
+/// `config.toml`: `[defaults]` apply to every mode, `[modes.<name>]` override them
+///
+/// every setting is optional and CLI flags take precedence over it
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    defaults: Settings,
+    modes: Modes,
+    /// `action = ["key", ...]`, replacing the default keys of the action
+    #[serde(deserialize_with = "keys")]
+    keys: Vec<(Action, Vec<KeySequence>)>,
+}
+
+/// per-mode overrides, named like the merged source tags
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct Modes {
+    android: Settings,
+    android_effect: Settings,
+    ios: Settings,
+    ios_effect: Settings,
+    dyeh_preview: Settings,
+    dyeh_editor: Settings,
+    file: Settings,
+    stdin: Settings,
+    replay: Settings,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Settings {
+    // app
+    pub poll_interval_ms: Option<u64>,
+    pub event_poll_interval_ms: Option<u64>,
+    #[serde(deserialize_with = "positive")]
+    pub ring_buffer_size: Option<usize>,
+    #[serde(deserialize_with = "overflow_policy")]
+    pub overflow_policy: Option<OverflowPolicy>,
+    #[serde(deserialize_with = "retention_limit")]
+    pub max_log_items: Option<usize>,
+    #[serde(deserialize_with = "retention_limit")]
+    pub max_log_bytes: Option<usize>,
+    #[serde(deserialize_with = "positive")]
+    pub horizontal_scroll_step: Option<usize>,
+    pub display_event_duration_ms: Option<u64>,
+    pub wrap: Option<bool>,
+    #[serde(deserialize_with = "detail_level")]
+    pub detail_level: Option<LogDetailLevel>,
+    pub mouse_capture: Option<bool>,
+    pub show_debug_logs: Option<bool>,
+    #[serde(deserialize_with = "color")]
+    pub mode_color: Option<Color>,
+    #[serde(deserialize_with = "theme")]
+    pub theme: Option<Theme>,
+
+    // filter
+    pub filter: Option<String>,
+    #[serde(deserialize_with = "filter_mode")]
+    pub filter_mode: Option<FilterMode>,
+    pub context: Option<usize>,
+    pub context_before: Option<usize>,
+    pub context_after: Option<usize>,
+
+    // providers
+    #[serde(deserialize_with = "parser")]
+    pub parser: Option<ParserKind>,
+    pub skip_existing: Option<bool>,
+    pub dyeh_dir: Option<PathBuf>,
+    pub dyeh_history: Option<usize>,
+    pub all_previews: Option<bool>,
+}
+
+impl Config {
+    /// load the config file, a missing file is an empty config
+    pub fn load() -> io::Result<Self> {
+        let Some(path) = config_path() else {
+            return Ok(Self::default());
+        };
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                return Err(io::Error::new(
+                    e.kind(),
+                    format!("Error: Cannot read config {}: {}", path.display(), e),
+                ));
+            }
+        };
+
+        Self::parse(&text).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Error: Invalid config {}\n{}", path.display(), e),
+            )
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /// settings of one mode, its overrides on top of the defaults
+    pub fn mode(&self, name: &str) -> Settings {
+        let modes = &self.modes;
+        let overrides = match name {
+            "android" => &modes.android,
+            "android-effect" => &modes.android_effect,
+            "ios" => &modes.ios,
+            "ios-effect" => &modes.ios_effect,
+            "dyeh-preview" => &modes.dyeh_preview,
+            "dyeh-editor" => &modes.dyeh_editor,
+            "file" => &modes.file,
+            "stdin" => &modes.stdin,
+            "replay" => &modes.replay,
+            _ => return self.defaults.clone(),
+        };
+        overrides.clone().or(&self.defaults)
+    }
+
+    /// the default keymap with the `[keys]` bindings applied
+    pub fn keymap(&self) -> Keymap {
+        let mut keymap = Keymap::default();
+        for (action, keys) in &self.keys {
+            keymap.bind(*action, keys.iter().cloned());
+        }
+        keymap
+    }
+
+    /// settings of a session, merged sources only share the defaults
+    pub fn session(&self, names: &[String]) -> Settings {
+        match names {
+            [name] => self.mode(name),
+            _ => self.defaults.clone(),
+        }
+    }
+}
+
+impl Settings {
+    /// fill the unset settings from `fallback`
+    fn or(self, fallback: &Settings) -> Settings {
+        let fallback = fallback.clone();
+        Settings {
+            poll_interval_ms: self.poll_interval_ms.or(fallback.poll_interval_ms),
+            event_poll_interval_ms: self
+                .event_poll_interval_ms
+                .or(fallback.event_poll_interval_ms),
+            ring_buffer_size: self.ring_buffer_size.or(fallback.ring_buffer_size),
+            overflow_policy: self.overflow_policy.or(fallback.overflow_policy),
+            max_log_items: self.max_log_items.or(fallback.max_log_items),
+            max_log_bytes: self.max_log_bytes.or(fallback.max_log_bytes),
+            horizontal_scroll_step: self
+                .horizontal_scroll_step
+                .or(fallback.horizontal_scroll_step),
+            display_event_duration_ms: self
+                .display_event_duration_ms
+                .or(fallback.display_event_duration_ms),
+            wrap: self.wrap.or(fallback.wrap),
+            detail_level: self.detail_level.or(fallback.detail_level),
+            mouse_capture: self.mouse_capture.or(fallback.mouse_capture),
+            show_debug_logs: self.show_debug_logs.or(fallback.show_debug_logs),
+            mode_color: self.mode_color.or(fallback.mode_color),
+            theme: self.theme.or(fallback.theme),
+            filter: self.filter.or(fallback.filter),
+            filter_mode: self.filter_mode.or(fallback.filter_mode),
+            context: self.context.or(fallback.context),
+            context_before: self.context_before.or(fallback.context_before),
+            context_after: self.context_after.or(fallback.context_after),
+            parser: self.parser.or(fallback.parser),
+            skip_existing: self.skip_existing.or(fallback.skip_existing),
+            dyeh_dir: self.dyeh_dir.or(fallback.dyeh_dir),
+            dyeh_history: self.dyeh_history.or(fallback.dyeh_history),
+            all_previews: self.all_previews.or(fallback.all_previews),
+        }
+    }
+
+    /// like grep, `context-before` and `context-after` take precedence over `context`
+    pub fn filter_context(&self) -> Option<FilterContext> {
+        if self.context.is_none() && self.context_before.is_none() && self.context_after.is_none() {
+            return None;
+        }
+        Some(FilterContext::new(
+            self.context_before.or(self.context).unwrap_or(0),
+            self.context_after.or(self.context).unwrap_or(0),
+        ))
+    }
+}
+
+/// `$XDG_CONFIG_HOME/lazylog/config.toml`, `~/.config/lazylog/config.toml` without it
+pub fn config_path() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+        .map(|dir| dir.join("lazylog").join("config.toml"))
+}
+
+// ============================================================================
+// Value parsing
+// ============================================================================
+// errors raised here are reported by toml with the line and column of the value
+
+fn positive<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<usize>, D::Error> {
+    match usize::deserialize(deserializer)? {
+        0 => Err(D::Error::custom("expected a value greater than 0")),
+        value => Ok(Some(value)),
+    }
+}
+
+/// `0` would evict every log on each update, no limit is spelled by leaving the key out
+fn retention_limit<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<usize>, D::Error> {
+    match usize::deserialize(deserializer)? {
+        0 => Err(D::Error::custom(
+            "expected a limit greater than 0, remove the key to keep every log",
+        )),
+        value => Ok(Some(value)),
+    }
+}
+
+fn detail_level<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<LogDetailLevel>, D::Error> {
+    let level = LogDetailLevel::deserialize(deserializer)?;
+    if level > MAX_DETAIL_LEVEL {
+        return Err(D::Error::custom(format!(
+            "detail level {} is out of range, expected 0-{}",
+            level, MAX_DETAIL_LEVEL
+        )));
+    }
+    Ok(Some(level))
+}
+
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Color>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    Color::from_str(&name).map(Some).map_err(|_| {
+        D::Error::custom(format!(
+            "unknown color `{}`, expected a name (e.g. `lightblue`), `#rrggbb` or an index 0-255",
+            name
+        ))
+    })
+}
+
+fn theme<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Theme>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    Theme::from_name(&name).map(Some).ok_or_else(|| {
+        D::Error::custom(format!(
+            "unknown theme `{}`, expected one of: {}",
+            name,
+            Theme::NAMES.join(", ")
+        ))
+    })
+}
+
+fn overflow_policy<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<OverflowPolicy>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    match name.as_str() {
+        "block" => Ok(Some(OverflowPolicy::Block)),
+        "drop-oldest" => Ok(Some(OverflowPolicy::DropOldest)),
+        "drop-newest" => Ok(Some(OverflowPolicy::DropNewest)),
+        _ => Err(D::Error::custom(format!(
+            "unknown overflow policy `{}`, expected one of: block, drop-oldest, drop-newest",
+            name
+        ))),
+    }
+}
+
+fn filter_mode<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<FilterMode>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    [
+        FilterMode::Literal,
+        FilterMode::CaseSensitive,
+        FilterMode::Regex,
+    ]
+    .into_iter()
+    .find(|mode| mode.label() == name)
+    .map(Some)
+    .ok_or_else(|| {
+        D::Error::custom(format!(
+            "unknown filter mode `{}`, expected one of: literal, case, regex",
+            name
+        ))
+    })
+}
+
+fn parser<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<ParserKind>, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    ParserKind::from_name(&name).map(Some).ok_or_else(|| {
+        D::Error::custom(format!(
+            "unknown parser `{}`, expected one of: {}",
+            name,
+            ParserKind::NAMES
+        ))
+    })
+}
+
+/// action name of a `[keys]` entry
+struct ActionName(Action);
+
+impl<'de> Deserialize<'de> for ActionName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Action::from_name(&name).map(ActionName).ok_or_else(|| {
+            let names: Vec<&str> = Action::ALL.iter().map(|action| action.name()).collect();
+            D::Error::custom(format!(
+                "unknown action `{}`, expected one of: {}",
+                name,
+                names.join(", ")
+            ))
+        })
+    }
+}
+
+/// one key binding of a `[keys]` entry
+struct Binding(KeySequence);
+
+impl<'de> Deserialize<'de> for Binding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keys = String::deserialize(deserializer)?;
+        keys.parse()
+            .map(Binding)
+            .map_err(|e| D::Error::custom(format!("invalid key binding `{}`: {}", keys, e)))
+    }
+}
+
+fn keys<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<(Action, Vec<KeySequence>)>, D::Error> {
+    struct KeysVisitor;
+
+    impl<'de> Visitor<'de> for KeysVisitor {
+        type Value = Vec<(Action, Vec<KeySequence>)>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a table of action names to key lists")
+        }
+
+        // visited in file order, so a key bound twice ends up with the later action
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut keys = Vec::new();
+            while let Some(ActionName(action)) = map.next_key()? {
+                let bindings: Vec<Binding> = map.next_value()?;
+                keys.push((action, bindings.into_iter().map(|b| b.0).collect()));
+            }
+            Ok(keys)
+        }
+    }
+
+    deserializer.deserialize_map(KeysVisitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(text: &str) -> String {
+        Config::parse(text).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_mode_overrides_defaults() {
+        let config = Config::parse(
+            r##"
+            [defaults]
+            wrap = false
+            detail-level = 2
+            context = 3
+            theme = "light"
+
+            [modes.android-effect]
+            detail-level = 0
+            mode-color = "#ff8800"
+            context-after = 1
+            "##,
+        )
+        .unwrap();
+
+        let android = config.mode("android-effect");
+        assert_eq!(android.wrap, Some(false));
+        assert_eq!(android.detail_level, Some(0));
+        assert_eq!(android.mode_color, Some(Color::Rgb(255, 136, 0)));
+        assert_eq!(android.filter_context(), Some(FilterContext::new(3, 1)));
+
+        let ios = config.mode("ios");
+        assert_eq!(ios.detail_level, Some(2));
+        assert_eq!(ios.mode_color, None);
+        assert_eq!(ios.theme.map(|theme| theme.name), Some("light"));
+
+        // merged sources only share the defaults
+        let merged = config.session(&["android-effect".into(), "ios".into()]);
+        assert_eq!(merged.detail_level, Some(2));
+        assert_eq!(Config::default().mode("file").filter_context(), None);
+    }
+
+    #[test]
+    fn test_errors_point_at_the_value() {
+        let error = parse_error("[defaults]\nwrap = true\nmode-color = \"purpel\"\n");
+        assert!(error.contains("line 3"), "{}", error);
+        assert!(error.contains("unknown color `purpel`"), "{}", error);
+
+        let error = parse_error("[modes.andriod]\nwrap = true\n");
+        assert!(error.contains("line 1"), "{}", error);
+        assert!(error.contains("unknown field `andriod`"), "{}", error);
+
+        assert!(parse_error("[defaults]\ndetail-level = 9\n").contains("expected 0-4"));
+        assert!(parse_error("[defaults]\nring-buffer-size = 0\n").contains("greater than 0"));
+        let error = parse_error("[modes.file]\nmax-log-items = 0\n");
+        assert!(error.contains("line 2"), "{}", error);
+        assert!(error.contains("remove the key"), "{}", error);
+        assert!(parse_error("[defaults]\nmax-log-bytes = 0\n").contains("limit greater than 0"));
+        assert!(parse_error("[defaults]\nparser = \"json\"\n").contains("unknown parser `json`"));
+        assert!(parse_error("[defaults]\nfilter-mode = \"glob\"\n").contains("literal, case"));
+        assert!(parse_error("[defaults]\nwarp = true\n").contains("unknown field `warp`"));
+        assert!(parse_error("[defaults]\ntheme = \"solarized\"\n").contains("dark, light, high"));
+        assert!(parse_error("[defaults]\nwrap = 1\n").contains("line 2"));
+
+        let error = parse_error("[keys]\nnext-log = [\"n\"]\nprev-log = [\"e\", \"ctrl-nope\"]\n");
+        assert!(error.contains("line 3"), "{}", error);
+        assert!(error.contains("unknown key `ctrl-nope`"), "{}", error);
+        let error = parse_error("[keys]\n\nnext-lgo = [\"n\"]\n");
+        assert!(error.contains("line 3"), "{}", error);
+        assert!(error.contains("unknown action `next-lgo`"), "{}", error);
+    }
+
+    #[test]
+    fn test_keys_rebind_actions() {
+        let config = Config::parse(
+            r#"
+            [keys]
+            next-log = ["n", "down"]
+            prev-log = ["e", "up"]
+            jump-to-top = ["g g", "home"]
+            yank-all = []
+            "#,
+        )
+        .unwrap();
+
+        let keymap = config.keymap();
+        let keys = |action| {
+            keymap
+                .keys(action)
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(keys(Action::NextLog), vec!["n", "↓"]);
+        assert_eq!(keys(Action::JumpToTop), vec!["gg", "Home"]);
+        assert!(keys(Action::YankAll).is_empty());
+        // `n` was taken from next-match
+        assert!(keys(Action::NextMatch).is_empty());
+    }
+}
@@ -1,8 +1,18 @@
+mod config;
+
+use config::{Config, Settings};
 use crossterm::event;
 use lazylog_android::{AndroidEffectParser, AndroidLogProvider, AndroidParser};
 use lazylog_dyeh::{DyehEditorParser, DyehLogProvider, DyehParser};
-use lazylog_framework::provider::{LogItem, LogParser, LogProvider};
-use lazylog_framework::{AppDesc, start_with_desc};
+use lazylog_framework::provider::{
+    FileTailProvider, LogItem, LogParser, LogProvider, MultiProvider, PlainTextParser, RecordSplit,
+    RecordingProvider, ReplayProvider, ReplaySpeed, SOURCE_METADATA_KEY, Severity, StdinProvider,
+    merge_sources,
+};
+use lazylog_framework::{
+    AppDesc, ContextLine, ContextStream, FilterContext, FilterQuery, OverflowPolicy, TabDesc,
+    Theme, start_with_desc, start_with_tabs,
+};
 use lazylog_ios::{IosEffectParser, IosFullParser, IosLogProvider};
 use ratatui::{
     Terminal,
@@ -10,17 +20,22 @@ use ratatui::{
     crossterm::{
         event::{DisableMouseCapture, EnableMouseCapture},
         execute,
-        style::{Color, ResetColor, SetBackgroundColor, SetForegroundColor},
+        style::{
+            Attribute, Color, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+        },
         terminal::{
             Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
             enable_raw_mode,
         },
     },
 };
+use std::cell::Cell;
 use std::env;
-use std::io;
+use std::io::{self, IsTerminal};
 use std::panic;
+use std::path::PathBuf;
 use std::process::Command;
+use std::rc::Rc;
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
@@ -31,14 +46,47 @@ fn print_usage() {
     eprintln!("Options:");
     eprintln!("  --dyeh-preview, -dyp    Use DYEH file-based log provider");
     eprintln!("  --dyeh-editor, -dye     Use DYEH editor log provider");
+    eprintln!(
+        "  --dyeh-dir <PATH>       DYEH log directory (default: ~/Library/Application Support/DouyinAR)"
+    );
+    eprintln!(
+        "  --dyeh-history <N>      Load the N most recent rotated DYEH logs before following"
+    );
+    eprintln!("  --all-previews          Follow every live DYEH preview log, not only the newest");
     eprintln!("  --ios, -i               Use iOS log provider");
     eprintln!("  --ios-effect, -ie       Use iOS log provider [EFFECT MODE]");
     eprintln!("  --android, -a           Use Android log provider");
     eprintln!("  --android-effect, -ae   Use Android log provider [EFFECT MODE]");
+    eprintln!("  --file <PATH>           Follow a log file (handles truncation and rotation)");
+    eprintln!("  --stdin, -              Read logs piped to stdin");
+    eprintln!("  --replay <FILE>         Play back a capture written with --record");
+    eprintln!(
+        "  --parser <NAME>         Parser for --file/--stdin/--replay: {}",
+        ParserKind::NAMES
+    );
+    eprintln!("  --skip-existing         Only show lines appended to the file after startup");
+    eprintln!("  --speed <FACTOR>        Replay speed: instant (default), 1 = original, 2 = 2x");
+    eprintln!("  --record <FILE>         Record the raw logs of this session to a capture file");
+    eprintln!(
+        "  --tabs                  Open each provider option in its own tab instead of merging"
+    );
     eprintln!("  --headless              Stream logs to stdout without the TUI");
+    eprintln!(
+        "  --theme <NAME>          Color theme: {} (default: dark)",
+        Theme::NAMES.join(", ")
+    );
     eprintln!("  --filter, -f <QUERY>    Apply filter on startup");
+    eprintln!("  -A <N>                  Show N logs after each filter match");
+    eprintln!("  -B <N>                  Show N logs before each filter match");
+    eprintln!("  -C <N>                  Show N logs before and after each filter match");
     eprintln!("  --version, -v           Print version information");
     eprintln!("  --help, -h              Print this help message");
+    eprintln!();
+    eprintln!("Provider options can be combined to merge their logs, e.g. `lazylog -a -dye`,");
+    eprintln!("or opened side by side in tabs with `lazylog -a -dye --tabs`.");
+    eprintln!();
+    eprintln!("Defaults are read from $XDG_CONFIG_HOME/lazylog/config.toml (~/.config/lazylog/),");
+    eprintln!("options given here take precedence.");
 }
 
 fn check_idevicesyslog_available() -> io::Result<()> {
@@ -84,7 +132,7 @@ fn check_adb_available() -> io::Result<()> {
     }
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 enum UsageOptions {
     DyehPreview,
     DyehEditor,
@@ -92,79 +140,188 @@ enum UsageOptions {
     IosFull,
     Android,
     AndroidEffect,
-    Help,
-    Version,
-    None, // when no args provided, show help
+    File,
+    Stdin,
+    Replay,
 }
 
-fn get_mode_name(option: &UsageOptions) -> Option<String> {
+fn get_mode_name(option: &UsageOptions) -> &'static str {
     use UsageOptions::*;
     match option {
-        DyehPreview => Some("dyeh preview".to_string()),
-        DyehEditor => Some("dyeh editor".to_string()),
-        IosEffect => Some("ios effect".to_string()),
-        IosFull => Some("ios".to_string()),
-        Android => Some("android".to_string()),
-        AndroidEffect => Some("android effect".to_string()),
-        Help | Version | None => Option::None,
+        DyehPreview => "dyeh preview",
+        DyehEditor => "dyeh editor",
+        IosEffect => "ios effect",
+        IosFull => "ios",
+        Android => "android",
+        AndroidEffect => "android effect",
+        File => "file",
+        Stdin => "stdin",
+        Replay => "replay",
     }
 }
 
-fn set_provider_option(
-    current: &mut UsageOptions,
-    new_value: UsageOptions,
-) -> Result<(), io::Error> {
-    if matches!(current, UsageOptions::None | UsageOptions::Help) {
-        *current = new_value;
-        return Ok(());
+/// several provider options are merged into one stream, repeating one has no effect
+fn add_provider_option(current: &mut Vec<UsageOptions>, new_value: UsageOptions) {
+    if !current.contains(&new_value) {
+        current.push(new_value);
     }
+}
 
-    if *current == new_value {
-        return Ok(());
+/// name of a mode in merged source tags and the config's `[modes.<name>]` tables
+fn source_name(option: UsageOptions) -> String {
+    // tags double as filter values (`source:dyeh-editor`), so no spaces
+    get_mode_name(&option).replace(' ', "-")
+}
+
+/// parser for generic text sources (`--file`, `--stdin`, `--replay`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ParserKind {
+    Plain,
+    Dyeh,
+    DyehEditor,
+    Ios,
+    IosEffect,
+    Android,
+    AndroidEffect,
+}
+
+impl ParserKind {
+    const NAMES: &str = "plain, dyeh, dyeh-editor, ios, ios-effect, android, android-effect";
+
+    fn from_name(name: &str) -> Option<Self> {
+        use ParserKind::*;
+        match name {
+            "plain" => Some(Plain),
+            "dyeh" => Some(Dyeh),
+            "dyeh-editor" => Some(DyehEditor),
+            "ios" => Some(Ios),
+            "ios-effect" => Some(IosEffect),
+            "android" => Some(Android),
+            "android-effect" => Some(AndroidEffect),
+            _ => None,
+        }
     }
 
-    print_usage();
-    Err(io::Error::new(
-        io::ErrorKind::InvalidInput,
-        "Only one provider option can be used at a time",
-    ))
+    fn parser(self) -> Arc<dyn LogParser> {
+        use ParserKind::*;
+        match self {
+            Plain => Arc::new(PlainTextParser::new()),
+            Dyeh => Arc::new(DyehParser::new()),
+            DyehEditor => Arc::new(DyehEditorParser::new()),
+            Ios => Arc::new(IosFullParser::new()),
+            IosEffect => Arc::new(IosEffectParser::new()),
+            Android => Arc::new(AndroidParser::new()),
+            AndroidEffect => Arc::new(AndroidEffectParser::new()),
+        }
+    }
+
+    /// how lines of a text source are grouped into the records this parser expects
+    fn record_split(self) -> RecordSplit {
+        use ParserKind::*;
+        let start_pattern = match self {
+            Plain => return RecordSplit::Lines,
+            Dyeh => r"^## \d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}",
+            DyehEditor => r"^\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{3}\] \[[^\]]+\]",
+            Ios | IosEffect => r"^[A-Z][a-z]{2} +\d{1,2} \d{2}:\d{2}:\d{2}",
+            Android | AndroidEffect => r"^\[ \d{2}-\d{2} \d{2}:\d{2}:\d{2}",
+        };
+        RecordSplit::start_pattern(start_pattern).expect("record start patterns are valid")
+    }
 }
 
 struct CliOptions {
-    usage_option: UsageOptions,
+    /// provider options in the order given, several are merged into one stream (or tabs)
+    usage_options: Vec<UsageOptions>,
+    help: bool,
+    version: bool,
+    tabs: bool,
     headless: bool,
     initial_filter: Option<String>,
+    filter_context: Option<FilterContext>,
+    file_path: Option<PathBuf>,
+    parser_kind: Option<ParserKind>,
+    skip_existing: bool,
+    record_path: Option<PathBuf>,
+    replay_path: Option<PathBuf>,
+    replay_speed: Option<ReplaySpeed>,
+    dyeh_dir: Option<PathBuf>,
+    dyeh_history: Option<usize>,
+    all_previews: bool,
+    theme: Option<Theme>,
+}
+
+fn parse_replay_speed(value: &str) -> Option<ReplaySpeed> {
+    if value == "instant" {
+        return Some(ReplaySpeed::Instant);
+    }
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|factor| factor.is_finite() && *factor > 0.0)
+        .map(ReplaySpeed::Scaled)
+}
+
+fn parse_context_lines(option: &str, value: Option<&String>) -> Result<usize, io::Error> {
+    let value = value.ok_or_else(|| missing_value(option))?;
+    value.parse::<usize>().map_err(|_| {
+        print_usage();
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid context line count for {}: {}", option, value),
+        )
+    })
+}
+
+fn missing_value(option: &str) -> io::Error {
+    print_usage();
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("Missing value after {}", option),
+    )
 }
 
 impl CliOptions {
     fn from_args(args: &[String]) -> Result<Self, io::Error> {
-        let mut usage_option = UsageOptions::None;
+        let mut usage_options = Vec::new();
         let mut headless = false;
+        let mut tabs = false;
         let mut initial_filter = None;
+        let mut context_before = None;
+        let mut context_after = None;
+        let mut context_both = None;
+        let mut file_path = None;
+        let mut parser_kind = None;
+        let mut skip_existing = false;
+        let mut record_path = None;
+        let mut replay_path = None;
+        let mut replay_speed = None;
+        let mut dyeh_dir = None;
+        let mut dyeh_history = None;
+        let mut all_previews = false;
+        let mut theme = None;
         let mut help_requested = false;
+        let mut version_requested = false;
 
         let mut i = 0;
         while i < args.len() {
             match args[i].as_str() {
                 "--ios-effect" | "-ie" => {
-                    set_provider_option(&mut usage_option, UsageOptions::IosEffect)?
+                    add_provider_option(&mut usage_options, UsageOptions::IosEffect)
                 }
-                "--ios" | "-i" => set_provider_option(&mut usage_option, UsageOptions::IosFull)?,
+                "--ios" | "-i" => add_provider_option(&mut usage_options, UsageOptions::IosFull),
                 "--android" | "-a" => {
-                    set_provider_option(&mut usage_option, UsageOptions::Android)?
+                    add_provider_option(&mut usage_options, UsageOptions::Android)
                 }
                 "--android-effect" | "-ae" => {
-                    set_provider_option(&mut usage_option, UsageOptions::AndroidEffect)?
+                    add_provider_option(&mut usage_options, UsageOptions::AndroidEffect)
                 }
                 "--dyeh-preview" | "-dyp" => {
-                    set_provider_option(&mut usage_option, UsageOptions::DyehPreview)?
+                    add_provider_option(&mut usage_options, UsageOptions::DyehPreview)
                 }
                 "--dyeh-editor" | "-dye" => {
-                    set_provider_option(&mut usage_option, UsageOptions::DyehEditor)?
-                }
-                "--version" | "-v" => {
-                    set_provider_option(&mut usage_option, UsageOptions::Version)?
+                    add_provider_option(&mut usage_options, UsageOptions::DyehEditor)
                 }
+                "--version" | "-v" => version_requested = true,
                 "--filter" | "-f" => {
                     i += 1;
                     if i >= args.len() {
@@ -183,7 +340,100 @@ impl CliOptions {
                     }
                     initial_filter = Some(args[i].clone());
                 }
+                "-A" => {
+                    i += 1;
+                    context_after = Some(parse_context_lines("-A", args.get(i))?);
+                }
+                "-B" => {
+                    i += 1;
+                    context_before = Some(parse_context_lines("-B", args.get(i))?);
+                }
+                "-C" => {
+                    i += 1;
+                    context_both = Some(parse_context_lines("-C", args.get(i))?);
+                }
+                "--file" => {
+                    add_provider_option(&mut usage_options, UsageOptions::File);
+                    i += 1;
+                    let path = args.get(i).ok_or_else(|| missing_value("--file"))?;
+                    file_path = Some(PathBuf::from(path));
+                }
+                "--parser" => {
+                    i += 1;
+                    let name = args.get(i).ok_or_else(|| missing_value("--parser"))?;
+                    parser_kind = Some(ParserKind::from_name(name).ok_or_else(|| {
+                        print_usage();
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!(
+                                "Unknown parser: {} (expected one of: {})",
+                                name,
+                                ParserKind::NAMES
+                            ),
+                        )
+                    })?);
+                }
+                "--theme" => {
+                    i += 1;
+                    let name = args.get(i).ok_or_else(|| missing_value("--theme"))?;
+                    theme = Some(Theme::from_name(name).ok_or_else(|| {
+                        print_usage();
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!(
+                                "Unknown theme: {} (expected one of: {})",
+                                name,
+                                Theme::NAMES.join(", ")
+                            ),
+                        )
+                    })?);
+                }
+                "--stdin" | "-" => add_provider_option(&mut usage_options, UsageOptions::Stdin),
+                "--replay" => {
+                    add_provider_option(&mut usage_options, UsageOptions::Replay);
+                    i += 1;
+                    let path = args.get(i).ok_or_else(|| missing_value("--replay"))?;
+                    replay_path = Some(PathBuf::from(path));
+                }
+                "--record" => {
+                    i += 1;
+                    let path = args.get(i).ok_or_else(|| missing_value("--record"))?;
+                    record_path = Some(PathBuf::from(path));
+                }
+                "--speed" => {
+                    i += 1;
+                    let value = args.get(i).ok_or_else(|| missing_value("--speed"))?;
+                    replay_speed = Some(parse_replay_speed(value).ok_or_else(|| {
+                        print_usage();
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!(
+                                "Invalid speed: {} (expected `instant` or a positive factor)",
+                                value
+                            ),
+                        )
+                    })?);
+                }
+                "--dyeh-dir" => {
+                    i += 1;
+                    let path = args.get(i).ok_or_else(|| missing_value("--dyeh-dir"))?;
+                    dyeh_dir = Some(PathBuf::from(path));
+                }
+                "--dyeh-history" => {
+                    i += 1;
+                    let value = args.get(i).ok_or_else(|| missing_value("--dyeh-history"))?;
+                    dyeh_history = Some(value.parse::<usize>().map_err(|_| {
+                        print_usage();
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("Invalid history count: {}", value),
+                        )
+                    })?);
+                }
+                "--all-previews" => all_previews = true,
+                "--skip-existing" => skip_existing = true,
                 "--headless" => headless = true,
+                "--tabs" => tabs = true,
                 "--help" | "-h" => help_requested = true,
                 _ => {
                     print_usage();
@@ -196,14 +446,109 @@ impl CliOptions {
             i += 1;
         }
 
-        if help_requested {
-            usage_option = UsageOptions::Help;
+        let uses = |option: UsageOptions| usage_options.contains(&option);
+
+        if parser_kind.is_some()
+            && !uses(UsageOptions::File)
+            && !uses(UsageOptions::Stdin)
+            && !uses(UsageOptions::Replay)
+        {
+            print_usage();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--parser can only be used with --file, --stdin or --replay",
+            ));
+        }
+
+        if replay_speed.is_some() && !uses(UsageOptions::Replay) {
+            print_usage();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--speed can only be used with --replay",
+            ));
+        }
+
+        if record_path.is_some() && uses(UsageOptions::Replay) {
+            print_usage();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--record cannot be used with --replay",
+            ));
+        }
+
+        // a capture is replayed through a single parser
+        if record_path.is_some() && usage_options.len() > 1 {
+            print_usage();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--record can only be used with a single provider",
+            ));
+        }
+
+        if skip_existing && !uses(UsageOptions::File) {
+            print_usage();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--skip-existing can only be used with --file",
+            ));
+        }
+
+        if (dyeh_dir.is_some() || dyeh_history.is_some())
+            && !uses(UsageOptions::DyehPreview)
+            && !uses(UsageOptions::DyehEditor)
+        {
+            print_usage();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--dyeh-dir and --dyeh-history can only be used with --dyeh-preview or --dyeh-editor",
+            ));
         }
 
+        if tabs && (headless || record_path.is_some()) {
+            print_usage();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--tabs cannot be used with --headless or --record",
+            ));
+        }
+
+        if all_previews && !uses(UsageOptions::DyehPreview) {
+            print_usage();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--all-previews can only be used with --dyeh-preview",
+            ));
+        }
+
+        // like grep, -A and -B take precedence over -C
+        let filter_context = (context_before.is_some()
+            || context_after.is_some()
+            || context_both.is_some())
+        .then(|| {
+            FilterContext::new(
+                context_before.or(context_both).unwrap_or(0),
+                context_after.or(context_both).unwrap_or(0),
+            )
+        });
+
         Ok(Self {
-            usage_option,
+            usage_options,
+            help: help_requested,
+            version: version_requested,
+            tabs,
             headless,
             initial_filter,
+            filter_context,
+            file_path,
+            parser_kind,
+            skip_existing,
+            record_path,
+            replay_path,
+            replay_speed,
+            dyeh_dir,
+            dyeh_history,
+            all_previews,
+            theme,
         })
     }
 }
@@ -211,166 +556,415 @@ impl CliOptions {
 fn matches_filter(
     parser: &Arc<dyn LogParser>,
     item: &LogItem,
-    initial_filter: Option<&str>,
+    query: Option<&FilterQuery>,
 ) -> bool {
-    let Some(query) = initial_filter
-        .map(str::trim)
-        .filter(|query| !query.is_empty())
-    else {
-        return true;
-    };
+    query.is_none_or(|query| query.matches(parser.as_ref(), item, parser.max_detail_level()))
+}
 
-    parser
-        .get_searchable_text(item, parser.max_detail_level())
-        .to_lowercase()
-        .contains(&query.to_lowercase())
+fn print_headless_log(parser: &Arc<dyn LogParser>, item: &LogItem, is_context: bool) {
+    let color = get_headless_log_color(parser.severity(item));
+    // merged sources are prefixed with their name
+    let tag = item
+        .get_metadata(SOURCE_METADATA_KEY)
+        .map(|name| format!("[{}] ", name))
+        .unwrap_or_default();
+    if is_context {
+        println!(
+            "{}{}{}{}{}",
+            SetAttribute(Attribute::Dim),
+            SetForegroundColor(color),
+            tag,
+            item.raw_content,
+            ResetColor
+        );
+    } else {
+        println!(
+            "{}{}{}{}",
+            SetForegroundColor(color),
+            tag,
+            item.raw_content,
+            ResetColor
+        );
+    }
 }
 
-fn run_headless<P>(
-    mut provider: P,
-    parser: Arc<dyn LogParser>,
-    initial_filter: Option<&str>,
-    poll_interval: Duration,
-) -> io::Result<()>
+/// stream the logs to stdout, filtered like the TUI would be with `desc`
+fn run_headless<P>(mut provider: P, desc: &AppDesc) -> io::Result<()>
 where
     P: LogProvider,
 {
+    let parser = &desc.parser;
+    // parse once, the same query language as the TUI filter bar
+    let query = desc
+        .initial_filter
+        .as_deref()
+        .map(str::trim)
+        .filter(|query| !query.is_empty())
+        .map(|query| FilterQuery::parse(query, desc.filter_mode))
+        .transpose()
+        .map_err(|e| io::Error::other(format!("Invalid filter: {}", e)))?;
+
     provider.start().map_err(io::Error::other)?;
 
-    loop {
-        match provider.poll_logs() {
+    // without a filter every log matches, so context changes nothing
+    let mut context_stream = ContextStream::new(desc.filter_context);
+
+    // live providers stream forever, finite ones (stdin) end once their input is closed
+    while !provider.is_finished() {
+        match provider.poll_raw_logs() {
             Ok(raw_logs) => {
-                for raw_log in raw_logs {
-                    if let Some(item) = parser.parse(&raw_log)
-                        && matches_filter(&parser, &item, initial_filter)
-                    {
-                        let color = get_headless_log_color(&item);
-                        println!(
-                            "{}{}{}",
-                            SetForegroundColor(color),
-                            item.raw_content,
-                            ResetColor
-                        );
+                let items = raw_logs
+                    .iter()
+                    .filter_map(|raw_log| raw_log.parse(parser.as_ref()))
+                    .collect();
+                for item in merge_sources(items) {
+                    let is_match = matches_filter(parser, &item, query.as_ref());
+                    for line in context_stream.push(item, is_match) {
+                        match line {
+                            ContextLine::Match(item) => print_headless_log(parser, &item, false),
+                            ContextLine::Context(item) => print_headless_log(parser, &item, true),
+                            ContextLine::Divider => println!("--"),
+                        }
                     }
                 }
             }
             Err(err) => eprintln!("Provider poll error: {}", err),
         }
 
-        thread::sleep(poll_interval);
+        thread::sleep(desc.poll_interval);
+    }
+
+    provider.stop().map_err(io::Error::other)
+}
+
+fn get_headless_log_color(severity: Option<Severity>) -> Color {
+    match severity {
+        Some(Severity::Fatal | Severity::Error) => Color::Red,
+        Some(Severity::Warning) => Color::Yellow,
+        Some(Severity::Notice) => Color::White,
+        Some(Severity::Debug) => Color::Green,
+        Some(Severity::Verbose) => Color::DarkGrey,
+        Some(Severity::Info) | None => Color::Grey,
     }
 }
 
-fn get_headless_log_color(item: &LogItem) -> Color {
-    let level = item.get_metadata("level").unwrap_or("").to_uppercase();
-    match level.as_str() {
-        "ERROR" => Color::Red,
-        "WARNING" | "WARN" => Color::Yellow,
-        "SYSTEM" => Color::White,
-        _ => Color::Grey,
+/// log directory of the DYEH preview and editor apps, `--dyeh-dir`, the config or the default location
+fn dyeh_log_dir(cli_options: &CliOptions, settings: &Settings) -> io::Result<PathBuf> {
+    if let Some(dir) = cli_options.dyeh_dir.as_ref().or(settings.dyeh_dir.as_ref()) {
+        if !dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Error: DYEH log directory not found: {}", dir.display()),
+            ));
+        }
+        return Ok(dir.clone());
     }
+
+    dirs::home_dir()
+        .map(|dir| dir.join("Library/Application Support/DouyinAR"))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "Error: Could not determine home directory",
+            )
+        })
+}
+
+type LogSource = (Box<dyn LogProvider>, Arc<dyn LogParser>);
+
+/// rotated DYEH logs loaded before following, `--dyeh-history` or the config
+fn dyeh_history(cli_options: &CliOptions, settings: &Settings) -> usize {
+    cli_options
+        .dyeh_history
+        .or(settings.dyeh_history)
+        .unwrap_or(0)
+}
+
+/// build the provider and parser for one provider option
+fn build_source(
+    cli_options: &CliOptions,
+    config: &Config,
+    usage_option: UsageOptions,
+) -> io::Result<LogSource> {
+    let settings = config.mode(&source_name(usage_option));
+    let parser_kind = cli_options
+        .parser_kind
+        .or(settings.parser)
+        .unwrap_or(ParserKind::Plain);
+    let history = dyeh_history(cli_options, &settings);
+
+    let source: LogSource = match usage_option {
+        UsageOptions::IosEffect => (
+            Box::new(IosLogProvider::new()),
+            Arc::new(IosEffectParser::new()),
+        ),
+        UsageOptions::IosFull => (
+            Box::new(IosLogProvider::new()),
+            Arc::new(IosFullParser::new()),
+        ),
+        UsageOptions::Android => (
+            Box::new(AndroidLogProvider::new()),
+            Arc::new(AndroidParser::new()),
+        ),
+        UsageOptions::AndroidEffect => (
+            Box::new(AndroidLogProvider::new()),
+            Arc::new(AndroidEffectParser::new()),
+        ),
+        UsageOptions::DyehPreview => {
+            let log_dir = dyeh_log_dir(cli_options, &settings)?;
+            let provider = if cli_options.all_previews || settings.all_previews == Some(true) {
+                DyehLogProvider::new_multi(log_dir)
+            } else {
+                DyehLogProvider::new(log_dir)
+            };
+            let provider = provider.with_history(history);
+            (Box::new(provider), Arc::new(DyehParser::new()))
+        }
+        UsageOptions::DyehEditor => (
+            Box::new(
+                DyehLogProvider::new_editor(dyeh_log_dir(cli_options, &settings)?)
+                    .with_history(history),
+            ),
+            Arc::new(DyehEditorParser::new()),
+        ),
+        UsageOptions::File => {
+            let path = cli_options.file_path.clone().unwrap_or_default();
+            // a missing file is waited for, the provider opens it once it appears
+            if path.exists() && !path.is_file() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Error: Not a regular file: {}", path.display()),
+                ));
+            }
+
+            let skip_existing = cli_options.skip_existing || settings.skip_existing == Some(true);
+            let provider = FileTailProvider::new(path)
+                .read_existing(!skip_existing)
+                .record_split(parser_kind.record_split());
+            (Box::new(provider), parser_kind.parser())
+        }
+        UsageOptions::Stdin => {
+            if io::stdin().is_terminal() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Error: --stdin expects piped input, e.g. `some-command 2>&1 | lazylog --stdin`",
+                ));
+            }
+
+            let provider = StdinProvider::new().record_split(parser_kind.record_split());
+            (Box::new(provider), parser_kind.parser())
+        }
+        UsageOptions::Replay => {
+            let path = cli_options.replay_path.clone().unwrap_or_default();
+            let provider = ReplayProvider::open(&path)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Error: {:#}", e)))?
+                .speed(cli_options.replay_speed.unwrap_or_default());
+            (Box::new(provider), parser_kind.parser())
+        }
+    };
+
+    Ok(source)
+}
+
+/// build the selected sources, several are merged into one time-ordered stream
+fn build_sources(cli_options: &CliOptions, config: &Config) -> io::Result<LogSource> {
+    if let [usage_option] = cli_options.usage_options[..] {
+        return build_source(cli_options, config, usage_option);
+    }
+
+    let mut multi = MultiProvider::new();
+    for &usage_option in &cli_options.usage_options {
+        let (provider, parser) = build_source(cli_options, config, usage_option)?;
+        multi = multi.source(source_name(usage_option), provider, parser);
+    }
+
+    let parser = multi.parser();
+    Ok((Box::new(multi), parser))
 }
 
 fn main() -> io::Result<()> {
     // Collect args excluding the binary name
     let args: Vec<String> = env::args().skip(1).collect();
     let cli_options = CliOptions::from_args(&args)?;
-    let usage_option = cli_options.usage_option;
+    let uses = |option: UsageOptions| cli_options.usage_options.contains(&option);
 
-    if matches!(usage_option, UsageOptions::Version) {
+    if cli_options.version {
         println!("lazylog {}", env!("CARGO_PKG_VERSION"));
         return Ok(());
     }
 
-    if matches!(usage_option, UsageOptions::Help | UsageOptions::None) {
+    if cli_options.help || cli_options.usage_options.is_empty() {
         print_usage();
         return Ok(());
     }
 
-    let poll_interval = Duration::from_millis(20);
     // check if idevicesyslog is available for iOS options
-    if matches!(
-        usage_option,
-        UsageOptions::IosEffect | UsageOptions::IosFull
-    ) && let Err(e) = check_idevicesyslog_available()
+    if (uses(UsageOptions::IosEffect) || uses(UsageOptions::IosFull))
+        && let Err(e) = check_idevicesyslog_available()
     {
         eprintln!("{}", e);
         std::process::exit(1);
     }
 
     // check if adb is available for Android option
-    if matches!(
-        usage_option,
-        UsageOptions::Android | UsageOptions::AndroidEffect
-    ) && let Err(e) = check_adb_available()
+    if (uses(UsageOptions::Android) || uses(UsageOptions::AndroidEffect))
+        && let Err(e) = check_adb_available()
     {
         eprintln!("{}", e);
         std::process::exit(1);
     }
 
+    // a malformed config is reported before the terminal is set up
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if cli_options.tabs {
+        return run_tabs(cli_options, config);
+    }
+
+    // build the source up front so a bad path is reported before the TUI starts
+    let (provider, parser) = build_sources(&cli_options, &config)?;
+    let provider: Box<dyn LogProvider> =
+        match &cli_options.record_path {
+            Some(path) => Box::new(RecordingProvider::create(provider, path).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidInput, format!("Error: {:#}", e))
+            })?),
+            None => provider,
+        };
+
+    let desc = build_desc(&cli_options, &config, &cli_options.usage_options, parser);
+
     if cli_options.headless {
-        let initial_filter = cli_options.initial_filter.as_deref();
-        return match usage_option {
-            UsageOptions::IosEffect => run_headless(
-                IosLogProvider::new(),
-                Arc::new(IosEffectParser::new()),
-                initial_filter,
-                poll_interval,
-            ),
-            UsageOptions::IosFull => run_headless(
-                IosLogProvider::new(),
-                Arc::new(IosFullParser::new()),
-                initial_filter,
-                poll_interval,
-            ),
-            UsageOptions::Android => run_headless(
-                AndroidLogProvider::new(),
-                Arc::new(AndroidParser::new()),
-                initial_filter,
-                poll_interval,
-            ),
-            UsageOptions::AndroidEffect => run_headless(
-                AndroidLogProvider::new(),
-                Arc::new(AndroidEffectParser::new()),
-                initial_filter,
-                poll_interval,
-            ),
-            UsageOptions::DyehPreview => {
-                if let Some(dir) = dirs::home_dir() {
-                    let log_dir_path = dir.join("Library/Application Support/DouyinAR");
-                    run_headless(
-                        DyehLogProvider::new(log_dir_path),
-                        Arc::new(DyehParser::new()),
-                        initial_filter,
-                        poll_interval,
-                    )
-                } else {
-                    Err(io::Error::new(
-                        io::ErrorKind::NotFound,
-                        "Error: Could not determine home directory",
-                    ))
-                }
-            }
-            UsageOptions::DyehEditor => {
-                if let Some(dir) = dirs::home_dir() {
-                    let log_dir_path = dir.join("Library/Application Support/DouyinAR");
-                    run_headless(
-                        DyehLogProvider::new_editor(log_dir_path),
-                        Arc::new(DyehEditorParser::new()),
-                        initial_filter,
-                        poll_interval,
-                    )
-                } else {
-                    Err(io::Error::new(
-                        io::ErrorKind::NotFound,
-                        "Error: Could not determine home directory",
-                    ))
+        return run_headless(provider, &desc);
+    }
+
+    run_tui(|terminal| start_with_desc(terminal, provider, desc))
+}
+
+/// open every provider option in its own tab instead of merging them
+fn run_tabs(cli_options: CliOptions, config: Config) -> io::Result<()> {
+    // build the sources up front so a bad path is reported before the TUI starts,
+    // tabs build their own when opened
+    for &usage_option in &cli_options.usage_options {
+        build_source(&cli_options, &config, usage_option)?;
+    }
+
+    let cli_options = Rc::new(cli_options);
+    let config = Rc::new(config);
+    // a stdin reader thread can't be stopped, after its tab closes it still takes
+    // the next line, so stdin is read by a single tab and never reopened
+    let stdin_opened = Rc::new(Cell::new(false));
+    let sessions = cli_options
+        .usage_options
+        .iter()
+        .map(|&usage_option| {
+            let cli_options = cli_options.clone();
+            let config = config.clone();
+            let stdin_opened = stdin_opened.clone();
+            TabDesc::new(get_mode_name(&usage_option), move || {
+                if usage_option == UsageOptions::Stdin && stdin_opened.replace(true) {
+                    return Err(io::Error::other("stdin can only be read by one tab").into());
                 }
+                let (provider, parser) = build_source(&cli_options, &config, usage_option)?;
+                let desc = build_desc(&cli_options, &config, &[usage_option], parser);
+                Ok((provider, desc))
+            })
+        })
+        .collect();
+
+    run_tui(|terminal| start_with_tabs(terminal, sessions))
+}
+
+/// app configuration for the given provider options, command line over config over defaults
+fn build_desc(
+    cli_options: &CliOptions,
+    config: &Config,
+    usage_options: &[UsageOptions],
+    parser: Arc<dyn LogParser>,
+) -> AppDesc {
+    let names: Vec<String> = usage_options.iter().copied().map(source_name).collect();
+    let settings = config.session(&names);
+
+    let mut desc = AppDesc::new(parser);
+    desc.initial_filter = cli_options
+        .initial_filter
+        .clone()
+        .or_else(|| settings.filter.clone());
+    desc.filter_mode = settings.filter_mode.unwrap_or_default();
+    desc.filter_context = cli_options
+        .filter_context
+        .or_else(|| settings.filter_context())
+        .unwrap_or_default();
+    if let Some(interval) = settings.poll_interval_ms {
+        desc.poll_interval = Duration::from_millis(interval);
+    }
+    if let Some(interval) = settings.event_poll_interval_ms {
+        desc.event_poll_interval = Duration::from_millis(interval);
+    }
+    if let Some(size) = settings.ring_buffer_size {
+        desc.ring_buffer_size = size;
+    }
+    desc.max_log_items = settings.max_log_items;
+    desc.max_log_bytes = settings.max_log_bytes;
+    if let Some(step) = settings.horizontal_scroll_step {
+        desc.horizontal_scroll_step = step;
+    }
+    if let Some(duration) = settings.display_event_duration_ms {
+        desc.display_event_duration = Duration::from_millis(duration);
+    }
+    desc.text_wrapping = settings.wrap.unwrap_or(desc.text_wrapping);
+    desc.detail_level = settings.detail_level.unwrap_or(desc.detail_level);
+    desc.mouse_capture = settings.mouse_capture.unwrap_or(desc.mouse_capture);
+    desc.show_debug_logs = settings.show_debug_logs.unwrap_or(desc.show_debug_logs);
+    desc.mode_color = settings.mode_color;
+    if let Some(theme) = cli_options.theme.clone().or(settings.theme) {
+        desc.theme = theme;
+    }
+    desc.keymap = config.keymap();
+    desc.mode_name = Some(
+        usage_options
+            .iter()
+            .map(get_mode_name)
+            .collect::<Vec<_>>()
+            .join(" + "),
+    );
+    // several options are merged, their logs are tagged with the source name
+    if names.len() > 1 {
+        desc.source_colors = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (name.clone(), desc.theme.source_color(index)))
+            .collect();
+    }
+
+    let backfills = usage_options
+        .iter()
+        .any(|&usage_option| match usage_option {
+            UsageOptions::Replay => true,
+            UsageOptions::DyehPreview | UsageOptions::DyehEditor => {
+                dyeh_history(cli_options, &config.mode(&source_name(usage_option))) > 0
             }
-            UsageOptions::Help | UsageOptions::None | UsageOptions::Version => unreachable!(),
-        };
+            _ => false,
+        });
+    if let Some(policy) = settings.overflow_policy {
+        desc.overflow_policy = policy;
+    } else if backfills {
+        // an instant replay or a history backfill arrives in one burst,
+        // wait for the UI instead of dropping logs
+        desc.overflow_policy = OverflowPolicy::Block;
     }
+    desc
+}
 
+/// run the TUI, restoring the terminal afterwards (and on panic)
+fn run_tui<E: std::fmt::Debug>(
+    start: impl FnOnce(&mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<(), E>,
+) -> io::Result<()> {
     let mut terminal = setup_terminal()?;
 
     // Ensure we restore the terminal on panic
@@ -380,76 +974,7 @@ fn main() -> io::Result<()> {
         original_hook(panic_info);
     }));
 
-    let initial_filter = cli_options.initial_filter;
-
-    let build_desc = |parser: Arc<dyn lazylog_framework::provider::LogParser>,
-                      option: UsageOptions|
-     -> AppDesc {
-        let mut desc = AppDesc::new(parser);
-        desc.initial_filter = initial_filter.clone();
-        desc.poll_interval = poll_interval;
-        desc.mode_name = get_mode_name(&option);
-        desc
-    };
-
-    // Prepare provider and parser based on option (default to DYEH)
-    let app_result = match usage_option {
-        UsageOptions::IosEffect => {
-            let provider = IosLogProvider::new();
-            let parser: Arc<dyn lazylog_framework::provider::LogParser> =
-                Arc::new(IosEffectParser::new());
-            let desc = build_desc(parser, UsageOptions::IosEffect);
-            start_with_desc(&mut terminal, provider, desc)
-        }
-        UsageOptions::IosFull => {
-            let provider = IosLogProvider::new();
-            let parser: Arc<dyn lazylog_framework::provider::LogParser> =
-                Arc::new(IosFullParser::new());
-            let desc = build_desc(parser, UsageOptions::IosFull);
-            start_with_desc(&mut terminal, provider, desc)
-        }
-        UsageOptions::Android => {
-            let provider = AndroidLogProvider::new();
-            let parser: Arc<dyn lazylog_framework::provider::LogParser> =
-                Arc::new(AndroidParser::new());
-            let desc = build_desc(parser, UsageOptions::Android);
-            start_with_desc(&mut terminal, provider, desc)
-        }
-        UsageOptions::AndroidEffect => {
-            let provider = AndroidLogProvider::new();
-            let parser: Arc<dyn lazylog_framework::provider::LogParser> =
-                Arc::new(AndroidEffectParser::new());
-            let desc = build_desc(parser, UsageOptions::AndroidEffect);
-            start_with_desc(&mut terminal, provider, desc)
-        }
-        UsageOptions::DyehPreview => {
-            if let Some(dir) = dirs::home_dir() {
-                let log_dir_path = dir.join("Library/Application Support/DouyinAR");
-                let provider = DyehLogProvider::new(log_dir_path);
-                let parser: Arc<dyn lazylog_framework::provider::LogParser> =
-                    Arc::new(DyehParser::new());
-                let desc = build_desc(parser, UsageOptions::DyehPreview);
-                start_with_desc(&mut terminal, provider, desc)
-            } else {
-                eprintln!("Error: Could not determine home directory");
-                Ok(())
-            }
-        }
-        UsageOptions::DyehEditor => {
-            if let Some(dir) = dirs::home_dir() {
-                let log_dir_path = dir.join("Library/Application Support/DouyinAR");
-                let provider = DyehLogProvider::new_editor(log_dir_path);
-                let parser: Arc<dyn lazylog_framework::provider::LogParser> =
-                    Arc::new(DyehEditorParser::new());
-                let desc = build_desc(parser, UsageOptions::DyehEditor);
-                start_with_desc(&mut terminal, provider, desc)
-            } else {
-                eprintln!("Error: Could not determine home directory");
-                Ok(())
-            }
-        }
-        UsageOptions::Help | UsageOptions::None | UsageOptions::Version => unreachable!(),
-    };
+    let app_result = start(&mut terminal);
 
     // Always restore terminal before printing or exiting
     restore_terminal()?;
@@ -462,6 +987,12 @@ fn main() -> io::Result<()> {
 }
 
 fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
+    // with `--stdin` the pipe is stdin, crossterm then reads keys from the controlling
+    // terminal instead, fail early with a clear message if there is none
+    if !io::stdin().is_terminal() {
+        ensure_controlling_terminal()?;
+    }
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     // enter the alternate screen to not mess with the user's shell history
@@ -477,6 +1008,30 @@ fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<io::Stdout>>> {
     Terminal::new(backend)
 }
 
+#[cfg(unix)]
+fn ensure_controlling_terminal() -> io::Result<()> {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .map(drop)
+        .map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!(
+                    "Error: No terminal available for keyboard input ({}), use --headless instead",
+                    e
+                ),
+            )
+        })
+}
+
+#[cfg(not(unix))]
+fn ensure_controlling_terminal() -> io::Result<()> {
+    // console input is read from the console handle, not from stdin
+    Ok(())
+}
+
 fn restore_terminal() -> io::Result<()> {
     let mut stdout = io::stdout();
 
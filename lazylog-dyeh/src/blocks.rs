@@ -0,0 +1,58 @@
+use crate::provider::DyehLogSource;
+use lazylog_framework::provider::{RecordBuffer, RecordSplit};
+
+/// start of a preview block (`## 2024-01-01 10:00:00 ...`)
+const PREVIEW_BLOCK_START: &str = r"^## \d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}";
+
+/// start of an editor block (`[2024-01-01 10:00:00.000] [INFO] ...`)
+const EDITOR_BLOCK_START: &str = r"^\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{3}\] \[[^\]]+\]";
+
+/// Reassembles log blocks from the bytes appended to a followed file.
+///
+/// A poll can land in the middle of a write, so the last block is held back until
+/// the next block starts or the file stays quiet.
+pub(crate) fn block_buffer(source: DyehLogSource) -> RecordBuffer {
+    let start_pattern = match source {
+        DyehLogSource::Preview => PREVIEW_BLOCK_START,
+        DyehLogSource::Editor => EDITOR_BLOCK_START,
+    };
+    let split = RecordSplit::start_pattern(start_pattern).expect("block patterns are valid");
+    RecordBuffer::new(split)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_holds_back_half_written_block_and_split_character() {
+        let mut buffer = block_buffer(DyehLogSource::Preview);
+        let text = "## 2024-01-01 10:00:00 first\ndétail\n## 2024-01-01 10:00:01 second\n";
+        let bytes = text.as_bytes();
+        // cut inside the two-byte `é`
+        let cut = text.find('é').unwrap() + 1;
+
+        assert!(buffer.push(&bytes[..cut]).is_empty());
+        assert_eq!(
+            buffer.push(&bytes[cut..]),
+            vec!["## 2024-01-01 10:00:00 first\ndétail"]
+        );
+        assert!(buffer.has_pending());
+        assert_eq!(buffer.flush(), vec!["## 2024-01-01 10:00:01 second"]);
+        assert!(!buffer.has_pending());
+    }
+
+    #[test]
+    fn test_editor_continuation_lines_stay_in_block() {
+        let mut buffer = block_buffer(DyehLogSource::Editor);
+        assert!(
+            buffer
+                .push(b"[2024-01-01 10:00:00.000] [ERROR] failed\n  at frame")
+                .is_empty()
+        );
+        assert_eq!(
+            buffer.push(b" 1\n[2024-01-01 10:00:01.000] [INFO] ok\n"),
+            vec!["[2024-01-01 10:00:00.000] [ERROR] failed\n  at frame 1"]
+        );
+    }
+}
@@ -43,6 +43,66 @@ fn find_latest_file(files: Vec<PathBuf>, empty_message: &str) -> Result<PathBuf,
     Ok(dated_files.pop().unwrap().0)
 }
 
+/// live preview log: a `.log` file directly inside a 'previewLog' directory, excluding
+/// rotated logs like `file.1.log`
+pub fn is_live_preview_log(path: &Path) -> bool {
+    path.parent()
+        .and_then(|dir| dir.file_name())
+        .is_some_and(|dir_name| dir_name == "previewLog")
+        && is_live_log_name(path)
+}
+
+pub fn is_editor_log(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with("-editor.log"))
+}
+
+fn is_live_log_name(path: &Path) -> bool {
+    log_base_name(path).is_some() && rotated_base_name(path).is_none()
+}
+
+/// file name without the `.log` extension
+fn log_base_name(path: &Path) -> Option<&str> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_suffix(".log"))
+}
+
+/// base name of the live log a rotated log like `file.1.log` belongs to (`file`)
+fn rotated_base_name(path: &Path) -> Option<&str> {
+    let (base_name, suffix) = log_base_name(path)?.rsplit_once('.')?;
+    suffix.parse::<u32>().is_ok().then_some(base_name)
+}
+
+/// find the `count` most recently modified rotated logs of a live log (`file.log` ->
+/// `file.1.log`, `file.2.log`, ...), oldest first so they read in order
+pub fn find_rotated_logs(live_log: &Path, count: usize) -> Vec<PathBuf> {
+    let (Some(dir), Some(live_base_name)) = (live_log.parent(), log_base_name(live_log)) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut dated_files: Vec<(PathBuf, std::time::SystemTime)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && rotated_base_name(path) == Some(live_base_name))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).ok()?.modified().ok()?;
+            Some((path, modified))
+        })
+        .collect();
+
+    // newest first, keep `count`, then flip to reading order
+    dated_files.sort_by(|(_, a), (_, b)| b.cmp(a));
+    dated_files.truncate(count);
+    dated_files.reverse();
+
+    dated_files.into_iter().map(|(path, _)| path).collect()
+}
+
 /// recursively find all 'previewLog' directories under the given path (DYEH-specific)
 pub fn find_preview_log_dirs(base_path: &Path) -> Vec<PathBuf> {
     let mut preview_log_dirs = Vec::new();
@@ -69,49 +129,25 @@ pub fn find_preview_log_dirs(base_path: &Path) -> Vec<PathBuf> {
     preview_log_dirs
 }
 
+/// list the live log files in DYEH previewLog directories (unreadable directories are skipped)
+pub fn find_live_logs(preview_log_dirs: &[PathBuf]) -> Vec<PathBuf> {
+    preview_log_dirs
+        .iter()
+        .filter_map(|log_dir| fs::read_dir(log_dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_live_log_name(path))
+        .collect()
+}
+
 /// find the latest live log file from DYEH previewLog directories
 pub fn find_latest_live_log(preview_log_dirs: Vec<PathBuf>) -> Result<PathBuf, String> {
     if preview_log_dirs.is_empty() {
         return Err("No previewLog directories found.".to_string());
     }
 
-    let mut all_live_log_files = Vec::new();
-
-    // search for live log files in all preview log directories
-    for log_dir in &preview_log_dirs {
-        let entries = fs::read_dir(log_dir)
-            .map_err(|e| format!("Failed to read directory '{}': {}", log_dir.display(), e))?;
-
-        let live_log_files: Vec<PathBuf> = entries
-            .filter_map(|entry_result| {
-                entry_result.ok().and_then(|entry| {
-                    let path = entry.path();
-                    if !path.is_file() {
-                        return None;
-                    }
-
-                    let file_name = path.file_name()?.to_str()?;
-                    if !file_name.ends_with(".log") {
-                        return None;
-                    }
-
-                    let base_name = file_name.strip_suffix(".log").unwrap();
-                    if let Some(last_dot_pos) = base_name.rfind('.') {
-                        let suffix = &base_name[last_dot_pos + 1..];
-                        if suffix.parse::<u32>().is_ok() {
-                            return None; // exclude rotated logs like `file.1.log`
-                        }
-                    }
-                    Some(path)
-                })
-            })
-            .collect();
-
-        all_live_log_files.extend(live_log_files);
-    }
-
     find_latest_file(
-        all_live_log_files,
+        find_live_logs(&preview_log_dirs),
         "No live log files found in any previewLog directories.",
     )
 }
@@ -121,11 +157,7 @@ pub fn find_latest_editor_log(base_path: &Path) -> Result<PathBuf, String> {
     let mut editor_log_files = Vec::new();
     collect_matching_files(
         base_path,
-        &|path| {
-            path.file_name()
-                .and_then(|name| name.to_str())
-                .is_some_and(|name| name.ends_with("-editor.log"))
-        },
+        &|path| is_editor_log(path),
         &mut editor_log_files,
     );
 
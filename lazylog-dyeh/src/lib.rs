@@ -2,6 +2,7 @@
 //
 // This crate provides a LogProvider implementation for DYEH/DouyinAR logs.
 
+mod blocks;
 mod file_finder;
 mod parser;
 mod provider;
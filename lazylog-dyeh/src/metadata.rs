@@ -1,6 +1,6 @@
-use std::{ffi::CString, io, path::Path};
+use std::{fs, io, path::Path};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TimeSpec {
     pub sec: i64,
     pub nsec: i64,
@@ -12,24 +12,35 @@ pub struct MetaSnap {
     pub mtime: TimeSpec,
 }
 
-#[cfg(target_os = "macos")]
+#[cfg(unix)]
 pub fn stat_path(path: &Path) -> io::Result<MetaSnap> {
-    use libc::{stat as stat_t, stat};
-    use std::mem;
+    use std::os::unix::fs::MetadataExt;
 
-    let cpath = CString::new(path.to_str().unwrap())
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let meta = fs::metadata(path)?;
+    Ok(MetaSnap {
+        len: meta.len(),
+        mtime: TimeSpec {
+            sec: meta.mtime(),
+            nsec: meta.mtime_nsec(),
+        },
+    })
+}
 
-    let mut st: stat_t = unsafe { mem::zeroed() };
-    if unsafe { stat(cpath.as_ptr(), &mut st) } != 0 {
-        return Err(io::Error::last_os_error());
-    }
+#[cfg(not(unix))]
+pub fn stat_path(path: &Path) -> io::Result<MetaSnap> {
+    use std::time::UNIX_EPOCH;
+
+    let meta = fs::metadata(path)?;
+    let mtime = meta
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
 
     Ok(MetaSnap {
-        len: st.st_size as u64,
+        len: meta.len(),
         mtime: TimeSpec {
-            sec: st.st_mtime as i64,
-            nsec: st.st_mtime_nsec as i64,
+            sec: mtime.as_secs() as i64,
+            nsec: mtime.subsec_nanos() as i64,
         },
     })
 }
@@ -1,6 +1,6 @@
 use lazy_static::lazy_static;
 use lazylog_framework::provider::{LogDetailLevel, LogItem, LogParser};
-use lazylog_parser::process_delta;
+use lazylog_parser::{parse_local_timestamp, process_delta};
 use regex::Regex;
 
 lazy_static! {
@@ -77,12 +77,15 @@ impl LogParser for DyehParser {
         let level = item.get_metadata("level").unwrap_or("");
         let tag = item.get_metadata("tag").unwrap_or("");
         let origin = item.get_metadata("origin").unwrap_or("");
+        // set by the provider, tells concurrent previews apart
+        let file = item.get_metadata("file").unwrap_or("");
 
         let field_order = [
             ("time", time.as_str()),
             ("tag", tag),
             ("level", level),
             ("origin", origin),
+            ("file", file),
         ];
 
         match detail_level {
@@ -121,7 +124,7 @@ impl LogParser for DyehParser {
                 parts.join(" ")
             }
             _ => {
-                // all fields (time + level + tag + origin)
+                // all fields (time + level + tag + origin + file)
                 let mut parts = Vec::new();
                 for (_, field_value) in field_order.iter() {
                     if !field_value.is_empty() {
@@ -153,6 +156,9 @@ impl LogParser for DyehEditorParser {
         let captures = EDITOR_LOG_RE.captures(first_line)?;
 
         let level = captures.name("level")?.as_str().trim();
+        let timestamp = captures.name("timestamp").and_then(|timestamp| {
+            parse_local_timestamp(timestamp.as_str(), "%Y-%m-%d %H:%M:%S%.f")
+        });
         let first_content = captures.name("content")?.as_str();
 
         let mut content_lines = vec![first_content.to_string()];
@@ -160,7 +166,11 @@ impl LogParser for DyehEditorParser {
 
         let content = content_lines.join("\n").trim().to_string();
 
-        Some(LogItem::new(content, raw_log.to_string()).with_metadata("level", level.to_string()))
+        let item = LogItem::new(content, raw_log.to_string()).with_level(level);
+        Some(match timestamp {
+            Some(timestamp) => item.with_timestamp(timestamp),
+            None => item,
+        })
     }
 
     fn format_preview(&self, item: &LogItem, detail_level: LogDetailLevel) -> String {
@@ -1,34 +1,142 @@
-use crate::{file_finder, metadata};
+use crate::{blocks::block_buffer, file_finder, metadata};
 use anyhow::Result;
-use lazy_static::lazy_static;
-use lazylog_framework::provider::LogProvider;
+use lazylog_framework::provider::{LogProvider, RawLog, RecordBuffer};
 use memmap2::MmapOptions;
-use regex::Regex;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use std::{
     fs::File,
     path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    time::{Duration, Instant},
 };
 
-lazy_static! {
-    static ref EDITOR_LOG_HEADER_RE: Regex =
-        Regex::new(r"^\[\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{3}\] \[[^\]]+\]").unwrap();
-}
+/// how often the tree is rescanned when file watching is unavailable
+const FALLBACK_RESCAN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// metadata key naming the log file an item was read from
+const FILE_METADATA_KEY: &str = "file";
 
 /// log provider for DYEH logs (file-based)
+///
+/// Follows the newest log file by default, or every live preview log at once
+/// with [`DyehLogProvider::new_multi`]. Each log carries a `file` metadata entry
+/// naming the project and file it was read from.
 pub struct DyehLogProvider {
     log_dir_path: PathBuf,
-    log_file_path: PathBuf,
     source: DyehLogSource,
+    /// follow every live preview log instead of only the newest one
+    follow_all: bool,
+    /// followed log files, at most one unless `follow_all`
+    files: Vec<FollowedFile>,
+    /// number of rotated logs to backfill per followed file on start
+    history: usize,
+    /// logs for the next poll: rotated history, the rest of files no longer followed
+    queued: Vec<RawLog>,
+    watch: Option<LogWatch>,
+    /// the tree must be searched for log files again
+    rescan_needed: bool,
+    last_rescan: Instant,
+}
+
+/// read position in one followed log file
+struct FollowedFile {
+    path: PathBuf,
+    /// `file` metadata value, e.g. `MyEffect/preview.log`
+    label: String,
     last_len: u64,
     prev_meta: Option<metadata::MetaSnap>,
+    /// the file may have changed and needs a stat
+    dirty: bool,
+    buffer: RecordBuffer,
+}
+
+/// file system watcher on the log tree, events are drained in `poll_logs`
+struct LogWatch {
+    watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
 }
 
 #[derive(Clone, Copy)]
-enum DyehLogSource {
+pub(crate) enum DyehLogSource {
     Preview,
     Editor,
 }
 
+impl FollowedFile {
+    fn new(path: PathBuf, source: DyehLogSource) -> Self {
+        Self {
+            label: Self::label_for(&path),
+            path,
+            last_len: 0,
+            prev_meta: None,
+            dirty: true,
+            buffer: block_buffer(source),
+        }
+    }
+
+    fn to_raw_logs(&self, blocks: Vec<String>) -> impl Iterator<Item = RawLog> + '_ {
+        blocks
+            .into_iter()
+            .map(|block| RawLog::new(block).with_metadata(FILE_METADATA_KEY, self.label.as_str()))
+    }
+
+    /// preview logs are named after their project (`<project>/previewLog/<file>`)
+    fn label_for(path: &Path) -> String {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let project = path
+            .parent()
+            .filter(|dir| dir.file_name().is_some_and(|name| name == "previewLog"))
+            .and_then(Path::parent)
+            .and_then(Path::file_name);
+
+        match project {
+            Some(project) => format!("{}/{}", project.to_string_lossy(), file_name),
+            None => file_name,
+        }
+    }
+
+    /// blocks completed since the last read, restarting from the top after truncation
+    fn read_new_blocks(&mut self) -> Vec<String> {
+        let current_meta = match metadata::stat_path(&self.path) {
+            Ok(m) => m,
+            Err(_) => return Vec::new(),
+        };
+
+        if !metadata::has_changed(&self.prev_meta, &current_meta) {
+            return Vec::new();
+        }
+
+        let mut log_blocks = Vec::new();
+
+        // handle file truncation, what was held back belongs to the old content
+        if current_meta.len < self.last_len {
+            log_blocks.extend(self.buffer.flush());
+            self.last_len = 0;
+        }
+
+        if current_meta.len > self.last_len {
+            match DyehLogProvider::read_delta(&self.path, self.last_len, current_meta.len) {
+                Ok(bytes) => {
+                    let blocks = self.buffer.push(&bytes);
+                    log::debug!("DyehLogProvider: Read {} new log blocks", blocks.len());
+                    log_blocks.extend(blocks);
+                }
+                Err(e) => {
+                    log::debug!("DyehLogProvider: Error reading delta: {}", e);
+                }
+            }
+            self.last_len = current_meta.len;
+        }
+
+        self.prev_meta = Some(current_meta);
+        log_blocks
+    }
+}
+
 impl DyehLogProvider {
     pub fn new(log_dir_path: PathBuf) -> Self {
         Self::new_with_source(log_dir_path, DyehLogSource::Preview)
@@ -38,27 +146,270 @@ impl DyehLogProvider {
         Self::new_with_source(log_dir_path, DyehLogSource::Editor)
     }
 
+    /// follows every live preview log under the log directory at the same time
+    pub fn new_multi(log_dir_path: PathBuf) -> Self {
+        let log_dir_path = Self::canonical_dir(log_dir_path);
+        let files = Self::find_live_preview_logs(&log_dir_path)
+            .into_iter()
+            .inspect(|path| {
+                log::debug!("DyehLogProvider: Following log file: {}", path.display());
+            })
+            .map(|path| FollowedFile::new(path, DyehLogSource::Preview))
+            .collect();
+
+        Self {
+            follow_all: true,
+            files,
+            ..Self::empty(log_dir_path, DyehLogSource::Preview)
+        }
+    }
+
     fn new_with_source(log_dir_path: PathBuf, source: DyehLogSource) -> Self {
-        let log_file_path = match Self::find_latest_log_file(&log_dir_path, source) {
+        let log_dir_path = Self::canonical_dir(log_dir_path);
+        let files = match Self::find_latest_log_file(&log_dir_path, source) {
             Ok(path) => {
                 log::debug!(
                     "DyehLogProvider: Found initial log file: {}",
                     path.display()
                 );
-                path
+                vec![FollowedFile::new(path, source)]
             }
             Err(e) => {
                 log::debug!("DyehLogProvider: No log files found initially: {}", e);
-                log_dir_path.join("__no_log_file_yet__.log")
+                Vec::new()
             }
         };
 
+        Self {
+            files,
+            ..Self::empty(log_dir_path, source)
+        }
+    }
+
+    /// On start, first replay the `count` most recent rotated logs (`file.1.log`, ...)
+    /// of each followed file, oldest first, so lines from before a crash are not lost.
+    pub fn with_history(mut self, count: usize) -> Self {
+        self.history = count;
+        self
+    }
+
+    /// watcher events may carry resolved paths (FSEvents does), so the tree is followed
+    /// through its canonical path, a directory that doesn't exist yet is kept as given
+    fn canonical_dir(log_dir_path: PathBuf) -> PathBuf {
+        log_dir_path.canonicalize().unwrap_or(log_dir_path)
+    }
+
+    fn empty(log_dir_path: PathBuf, source: DyehLogSource) -> Self {
         Self {
             log_dir_path,
-            log_file_path,
             source,
-            last_len: 0,
-            prev_meta: None,
+            follow_all: false,
+            files: Vec::new(),
+            history: 0,
+            queued: Vec::new(),
+            watch: None,
+            rescan_needed: false,
+            last_rescan: Instant::now(),
+        }
+    }
+
+    /// the `Logs` and `Log` directories that hold DYEH log files
+    fn log_roots(log_dir_path: &Path) -> [PathBuf; 2] {
+        [log_dir_path.join("Logs"), log_dir_path.join("Log")]
+    }
+
+    fn find_live_preview_logs(log_dir_path: &Path) -> Vec<PathBuf> {
+        let preview_log_dirs: Vec<PathBuf> = Self::log_roots(log_dir_path)
+            .iter()
+            .filter(|root| root.exists())
+            .flat_map(|root| file_finder::find_preview_log_dirs(root))
+            .collect();
+
+        file_finder::find_live_logs(&preview_log_dirs)
+    }
+
+    fn start_watch(&self) -> notify::Result<LogWatch> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+
+        // the top directory is watched on its own so `Logs`/`Log` appearing later are noticed
+        watcher.watch(&self.log_dir_path, RecursiveMode::NonRecursive)?;
+        for root in Self::log_roots(&self.log_dir_path) {
+            if root.is_dir() {
+                watcher.watch(&root, RecursiveMode::Recursive)?;
+            }
+        }
+
+        Ok(LogWatch { watcher, events })
+    }
+
+    /// apply pending watcher events, returns false if the watcher is gone
+    fn drain_watch_events(&mut self) -> bool {
+        let Some(watch) = &self.watch else {
+            return false;
+        };
+
+        let mut events = Vec::new();
+        let mut connected = true;
+        loop {
+            match watch.events.try_recv() {
+                Ok(Ok(event)) => events.push(event),
+                Ok(Err(e)) => {
+                    log::debug!("DyehLogProvider: Watch error: {}", e);
+                    self.rescan_needed = true;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    connected = false;
+                    break;
+                }
+            }
+        }
+
+        for event in events {
+            self.handle_watch_event(event);
+        }
+
+        if !connected {
+            log::debug!("DyehLogProvider: Watcher disconnected, falling back to polling");
+            self.watch = None;
+        }
+        connected
+    }
+
+    fn handle_watch_event(&mut self, event: Event) {
+        if event.need_rescan() {
+            self.rescan_needed = true;
+        }
+
+        let is_write = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_));
+        for path in &event.paths {
+            if let Some(file) = self.files.iter_mut().find(|file| file.path == *path) {
+                // growth, truncation, removal
+                file.dirty = true;
+                continue;
+            }
+
+            if !is_write {
+                continue;
+            }
+
+            if path.is_dir() {
+                if matches!(event.kind, EventKind::Create(_)) {
+                    self.watch_new_root(path);
+                    // files may have been written before the new directory was watched
+                    self.rescan_needed = true;
+                }
+                continue;
+            }
+
+            if self.is_log_candidate(path) {
+                self.follow_candidate(path.clone());
+            }
+        }
+    }
+
+    fn watch_new_root(&mut self, path: &Path) {
+        let Some(watch) = &mut self.watch else {
+            return;
+        };
+
+        if Self::log_roots(&self.log_dir_path)
+            .iter()
+            .any(|root| root == path)
+            && let Err(e) = watch.watcher.watch(path, RecursiveMode::Recursive)
+        {
+            log::debug!("DyehLogProvider: Cannot watch {}: {}", path.display(), e);
+        }
+    }
+
+    fn is_log_candidate(&self, path: &Path) -> bool {
+        let in_log_tree = Self::log_roots(&self.log_dir_path)
+            .iter()
+            .any(|root| path.starts_with(root));
+
+        in_log_tree
+            && match self.source {
+                DyehLogSource::Preview => file_finder::is_live_preview_log(path),
+                DyehLogSource::Editor => file_finder::is_editor_log(path),
+            }
+    }
+
+    /// a log file that is not followed yet was written to
+    fn follow_candidate(&mut self, path: PathBuf) {
+        if self.follow_all {
+            log::debug!("DyehLogProvider: Following log file: {}", path.display());
+            self.files.push(FollowedFile::new(path, self.source));
+        } else if self.is_newer_than_current(&path) {
+            self.switch_to_log_file(path);
+        }
+    }
+
+    fn is_newer_than_current(&self, path: &Path) -> bool {
+        let Ok(candidate) = metadata::stat_path(path) else {
+            return false;
+        };
+
+        match self
+            .files
+            .first()
+            .and_then(|file| metadata::stat_path(&file.path).ok())
+        {
+            Some(current) => candidate.mtime >= current.mtime,
+            None => true,
+        }
+    }
+
+    /// blocks of the rotated logs of every followed file, labeled like the live file
+    fn read_history(&self) -> Vec<RawLog> {
+        let mut raw_logs = Vec::new();
+        if self.history == 0 {
+            return raw_logs;
+        }
+
+        for file in &self.files {
+            for rotated in file_finder::find_rotated_logs(&file.path, self.history) {
+                let bytes = metadata::stat_path(&rotated)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|meta| Self::read_delta(&rotated, 0, meta.len));
+                match bytes {
+                    Ok(bytes) => {
+                        // a rotated log is complete, nothing is held back
+                        let mut buffer = block_buffer(self.source);
+                        let mut blocks = buffer.push(&bytes);
+                        blocks.extend(buffer.flush());
+                        log::debug!(
+                            "DyehLogProvider: Read {} history blocks from {}",
+                            blocks.len(),
+                            rotated.display()
+                        );
+                        raw_logs.extend(file.to_raw_logs(blocks));
+                    }
+                    Err(e) => {
+                        log::debug!(
+                            "DyehLogProvider: Error reading {}: {}",
+                            rotated.display(),
+                            e
+                        );
+                    }
+                }
+            }
+        }
+
+        raw_logs
+    }
+
+    fn rescan(&mut self) {
+        self.last_rescan = Instant::now();
+
+        if self.follow_all {
+            for path in Self::find_live_preview_logs(&self.log_dir_path) {
+                if !self.files.iter().any(|file| file.path == path) {
+                    self.follow_candidate(path);
+                }
+            }
+        } else if let Ok(Some(newer_file)) = self.check_for_newer_log_file() {
+            self.switch_to_log_file(newer_file);
         }
     }
 
@@ -118,17 +469,20 @@ impl DyehLogProvider {
     fn check_for_newer_log_file(&self) -> Result<Option<PathBuf>> {
         match Self::find_latest_log_file(&self.log_dir_path, self.source) {
             Ok(latest_file_path) => {
-                if !self.log_file_path.exists() {
+                let current = self.files.first().filter(|file| file.path.exists());
+                if let Some(current) = current
+                    && latest_file_path != current.path
+                {
                     log::debug!(
-                        "DyehLogProvider: Found first log file: {}",
-                        latest_file_path.display()
+                        "DyehLogProvider: Found newer log file: {} (current: {})",
+                        latest_file_path.display(),
+                        current.path.display()
                     );
                     Ok(Some(latest_file_path))
-                } else if latest_file_path != self.log_file_path {
+                } else if current.is_none() {
                     log::debug!(
-                        "DyehLogProvider: Found newer log file: {} (current: {})",
-                        latest_file_path.display(),
-                        self.log_file_path.display()
+                        "DyehLogProvider: Found first log file: {}",
+                        latest_file_path.display()
                     );
                     Ok(Some(latest_file_path))
                 } else {
@@ -143,160 +497,302 @@ impl DyehLogProvider {
     }
 
     fn switch_to_log_file(&mut self, new_file_path: PathBuf) {
-        log::debug!(
-            "DyehLogProvider: Switching from {} to {}",
-            self.log_file_path.display(),
-            new_file_path.display()
-        );
-        self.log_file_path = new_file_path;
-        self.last_len = 0;
-        self.prev_meta = None;
+        if let Some(current) = self.files.first() {
+            log::debug!(
+                "DyehLogProvider: Switching from {} to {}",
+                current.path.display(),
+                new_file_path.display()
+            );
+        }
+        let new_file = FollowedFile::new(new_file_path, self.source);
+        for mut previous in std::mem::replace(&mut self.files, vec![new_file]) {
+            self.queue_rest_of(&mut previous);
+        }
     }
 
-    fn read_delta(file_path: &Path, prev_len: u64, cur_len: u64) -> Result<Vec<String>> {
+    /// queue the blocks still held back for a file that is no longer followed
+    fn queue_rest_of(&mut self, file: &mut FollowedFile) {
+        let blocks = file.buffer.flush();
+        self.queued.extend(file.to_raw_logs(blocks));
+    }
+
+    fn read_delta(file_path: &Path, prev_len: u64, cur_len: u64) -> Result<Vec<u8>> {
         let file = File::open(file_path)?;
         let mmap = unsafe { MmapOptions::new().len(cur_len as usize).map(&file)? };
 
         let start = (prev_len as usize).min(mmap.len());
         let end = (cur_len as usize).min(mmap.len());
-        let delta_bytes = &mmap[start..end];
-
-        if delta_bytes.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let delta_str = String::from_utf8_lossy(delta_bytes);
+        Ok(mmap[start..end].to_vec())
+    }
+}
 
-        let log_blocks = Self::split_log_blocks(file_path, &delta_str);
+impl LogProvider for DyehLogProvider {
+    fn start(&mut self) -> Result<()> {
+        log::debug!("DyehLogProvider: Starting");
 
-        Ok(log_blocks)
+        self.watch = match self.start_watch() {
+            Ok(watch) => Some(watch),
+            Err(e) => {
+                log::debug!(
+                    "DyehLogProvider: Cannot watch {}, falling back to polling: {}",
+                    self.log_dir_path.display(),
+                    e
+                );
+                None
+            }
+        };
+        // catch files created between construction and the watch being set up
+        self.rescan_needed = true;
+        self.queued = self.read_history();
+        Ok(())
     }
 
-    fn split_log_blocks(file_path: &Path, text: &str) -> Vec<String> {
-        match Self::source_from_path(file_path) {
-            DyehLogSource::Preview => Self::split_preview_blocks(text),
-            DyehLogSource::Editor => Self::split_editor_blocks(text),
-        }
+    fn stop(&mut self) -> Result<()> {
+        log::debug!("DyehLogProvider: Stopping");
+        self.watch = None;
+        Ok(())
     }
 
-    fn source_from_path(file_path: &Path) -> DyehLogSource {
-        let is_editor = file_path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .is_some_and(|name| name.ends_with("-editor.log"));
-
-        if is_editor {
-            DyehLogSource::Editor
-        } else {
-            DyehLogSource::Preview
-        }
+    fn poll_logs(&mut self) -> Result<Vec<String>> {
+        Ok(self
+            .poll_raw_logs()?
+            .into_iter()
+            .map(|raw_log| raw_log.text)
+            .collect())
     }
 
-    fn split_preview_blocks(text: &str) -> Vec<String> {
-        lazy_static! {
-            static ref MARKER_RE: Regex =
-                Regex::new(r"## \d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}").unwrap();
+    fn poll_raw_logs(&mut self) -> Result<Vec<RawLog>> {
+        if !self.drain_watch_events() {
+            // without a watcher: stat every poll, rescan the tree now and then
+            for file in &mut self.files {
+                file.dirty = true;
+            }
+            if self.last_rescan.elapsed() >= FALLBACK_RESCAN_INTERVAL {
+                self.rescan_needed = true;
+            }
         }
 
-        let mut blocks = Vec::new();
-        let mut starts: Vec<usize> = MARKER_RE.find_iter(text).map(|m| m.start()).collect();
-
-        if starts.is_empty() {
-            return blocks;
+        if self.rescan_needed {
+            self.rescan_needed = false;
+            self.rescan();
         }
 
-        // add sentinel for the last block
-        starts.push(text.len());
+        if self.follow_all {
+            // sessions come and go, forget the files that were removed
+            let (kept, removed) = std::mem::take(&mut self.files)
+                .into_iter()
+                .partition(|file| file.path.exists());
+            self.files = kept;
+            for mut file in removed {
+                self.queue_rest_of(&mut file);
+            }
+        }
 
-        // extract each block from one ## to the next ##
-        for window in starts.windows(2) {
-            if let [start, end] = *window {
-                let block = text[start..end].trim().to_string();
-                if !block.is_empty() {
-                    blocks.push(block);
-                }
+        // rotated history and the rest of dropped files go out before the live files
+        let mut raw_logs = std::mem::take(&mut self.queued);
+        for file in &mut self.files {
+            let mut blocks = Vec::new();
+            if file.dirty {
+                file.dirty = false;
+                blocks = file.read_new_blocks();
             }
+            blocks.extend(file.buffer.flush_if_quiet());
+            raw_logs.extend(file.to_raw_logs(blocks));
         }
 
-        blocks
+        Ok(raw_logs)
     }
+}
 
-    fn split_editor_blocks(text: &str) -> Vec<String> {
-        let mut blocks = Vec::new();
-        let mut current_block = String::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, io::Write, thread};
 
-        for line in text.lines() {
-            let is_new_entry = EDITOR_LOG_HEADER_RE.is_match(line);
+    fn temp_log_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("lazylog-dyeh-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
 
-            if is_new_entry && !current_block.is_empty() {
-                blocks.push(current_block.trim().to_string());
-                current_block.clear();
-            }
+    fn append(path: &Path, text: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
 
-            if !current_block.is_empty() {
-                current_block.push('\n');
+    /// poll until `count` blocks arrived, watcher events are delivered asynchronously
+    fn poll_blocks(provider: &mut DyehLogProvider, count: usize) -> Vec<String> {
+        let mut blocks = Vec::new();
+        for _ in 0..400 {
+            blocks.extend(provider.poll_logs().unwrap());
+            if blocks.len() >= count {
+                return blocks;
             }
-            current_block.push_str(line);
+            thread::sleep(Duration::from_millis(5));
         }
+        panic!("expected {} blocks, got {:?}", count, blocks);
+    }
 
-        if !current_block.is_empty() {
-            blocks.push(current_block.trim().to_string());
-        }
+    #[test]
+    fn test_follows_growth_and_new_preview_logs() {
+        let dir = temp_log_dir();
+        let first = dir.join("Logs/project/previewLog/first.log");
+        append(&first, "## 2024-01-01 10:00:00 one\n");
+
+        let mut provider = DyehLogProvider::new(dir.clone());
+        provider.start().unwrap();
+        assert_eq!(
+            poll_blocks(&mut provider, 1),
+            vec!["## 2024-01-01 10:00:00 one"]
+        );
 
-        blocks
+        append(&first, "## 2024-01-01 10:00:01 two\n");
+        assert_eq!(
+            poll_blocks(&mut provider, 1),
+            vec!["## 2024-01-01 10:00:01 two"]
+        );
+
+        // a new session in a tree that did not exist when the provider started
+        append(
+            &dir.join("Log/other/previewLog/second.log"),
+            "## 2024-01-01 10:00:02 three\n",
+        );
+        assert_eq!(
+            poll_blocks(&mut provider, 1),
+            vec!["## 2024-01-01 10:00:02 three"]
+        );
+
+        provider.stop().unwrap();
+        fs::remove_dir_all(&dir).ok();
     }
-}
 
-impl LogProvider for DyehLogProvider {
-    fn start(&mut self) -> Result<()> {
-        log::debug!("DyehLogProvider: Starting");
-        Ok(())
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_log_dir_matches_resolved_event_paths() {
+        let dir = temp_log_dir();
+        let link = dir.with_extension("link");
+        std::os::unix::fs::symlink(&dir, &link).unwrap();
+        let log = link.join("Logs/project/previewLog/preview.log");
+        append(&log, "## 2024-01-01 10:00:00 one\n");
+
+        let provider = DyehLogProvider::new_multi(link.clone());
+        let resolved = log.canonicalize().unwrap();
+        assert_eq!(provider.files[0].path, resolved);
+        assert!(provider.is_log_candidate(&resolved.with_file_name("other.log")));
+
+        fs::remove_file(&link).ok();
+        fs::remove_dir_all(&dir).ok();
     }
 
-    fn stop(&mut self) -> Result<()> {
-        log::debug!("DyehLogProvider: Stopping");
-        Ok(())
+    #[test]
+    fn test_truncation_restarts_from_beginning() {
+        let dir = temp_log_dir();
+        let log = dir.join("Logs/project/previewLog/session.log");
+        append(&log, "## 2024-01-01 10:00:00 a long first block\n");
+
+        let mut provider = DyehLogProvider::new(dir.clone());
+        provider.start().unwrap();
+        poll_blocks(&mut provider, 1);
+
+        fs::write(&log, "## 2024-01-01 10:00:01 short\n").unwrap();
+        assert_eq!(
+            poll_blocks(&mut provider, 1),
+            vec!["## 2024-01-01 10:00:01 short"]
+        );
+
+        provider.stop().unwrap();
+        fs::remove_dir_all(&dir).ok();
     }
 
-    fn poll_logs(&mut self) -> Result<Vec<String>> {
-        // check for newer log file
-        if let Ok(Some(newer_file)) = self.check_for_newer_log_file() {
-            self.switch_to_log_file(newer_file);
-        }
+    #[test]
+    fn test_multi_follows_every_preview_with_file_metadata() {
+        let dir = temp_log_dir();
+        append(
+            &dir.join("Logs/alpha/previewLog/preview.log"),
+            "## 2024-01-01 10:00:00 from alpha\n",
+        );
+
+        let mut provider = DyehLogProvider::new_multi(dir.clone());
+        provider.start().unwrap();
+        append(
+            &dir.join("Logs/beta/previewLog/preview.log"),
+            "## 2024-01-01 10:00:01 from beta\n",
+        );
 
-        if !self.log_file_path.exists() {
-            return Ok(Vec::new());
+        let mut raw_logs = Vec::new();
+        for _ in 0..400 {
+            raw_logs.extend(provider.poll_raw_logs().unwrap());
+            if raw_logs.len() >= 2 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(5));
         }
 
-        let current_meta = match metadata::stat_path(&self.log_file_path) {
-            Ok(m) => m,
-            Err(_) => return Ok(Vec::new()),
-        };
+        let mut tagged: Vec<(String, String)> = raw_logs
+            .into_iter()
+            .map(|raw_log| (raw_log.metadata[0].1.clone(), raw_log.text))
+            .collect();
+        tagged.sort();
+        assert_eq!(
+            tagged,
+            vec![
+                (
+                    "alpha/preview.log".to_string(),
+                    "## 2024-01-01 10:00:00 from alpha".to_string()
+                ),
+                (
+                    "beta/preview.log".to_string(),
+                    "## 2024-01-01 10:00:01 from beta".to_string()
+                ),
+            ]
+        );
 
-        if !metadata::has_changed(&self.prev_meta, &current_meta) {
-            return Ok(Vec::new());
-        }
+        provider.stop().unwrap();
+        fs::remove_dir_all(&dir).ok();
+    }
 
-        // handle file truncation
-        if current_meta.len < self.last_len {
-            self.last_len = 0;
+    #[test]
+    fn test_history_backfills_rotated_editor_logs_in_order() {
+        let dir = temp_log_dir();
+        let logs = dir.join("Logs/editor");
+        let now = std::time::SystemTime::now();
+        for (name, line, age) in [
+            ("app-editor.3.log", "oldest", 30),
+            ("app-editor.2.log", "older", 20),
+            ("app-editor.1.log", "recent", 10),
+            ("app-editor.log", "live", 0),
+        ] {
+            let path = logs.join(name);
+            append(
+                &path,
+                &format!("[2024-01-01 10:00:00.000] [INFO] {}\n", line),
+            );
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - Duration::from_secs(age))
+                .unwrap();
         }
 
-        let mut log_blocks = Vec::new();
-        if current_meta.len > self.last_len {
-            match Self::read_delta(&self.log_file_path, self.last_len, current_meta.len) {
-                Ok(blocks) => {
-                    log::debug!("DyehLogProvider: Read {} new log blocks", blocks.len());
-                    log_blocks = blocks;
-                }
-                Err(e) => {
-                    log::debug!("DyehLogProvider: Error reading delta: {}", e);
-                }
-            }
-            self.last_len = current_meta.len;
-        }
+        let mut provider = DyehLogProvider::new_editor(dir.clone()).with_history(2);
+        provider.start().unwrap();
+        let blocks = poll_blocks(&mut provider, 3);
+        assert_eq!(
+            blocks,
+            vec![
+                "[2024-01-01 10:00:00.000] [INFO] older",
+                "[2024-01-01 10:00:00.000] [INFO] recent",
+                "[2024-01-01 10:00:00.000] [INFO] live",
+            ]
+        );
 
-        self.prev_meta = Some(current_meta);
-        Ok(log_blocks)
+        provider.stop().unwrap();
+        fs::remove_dir_all(&dir).ok();
     }
 }
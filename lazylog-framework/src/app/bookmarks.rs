@@ -0,0 +1,113 @@
+use super::App;
+use crate::keymap::Action;
+use anyhow::Result;
+
+impl App {
+    pub(super) fn is_bookmarked(&self, raw_idx: usize) -> bool {
+        !self.bookmarks.is_empty() && self.bookmarks.contains(&self.raw_logs[raw_idx].id)
+    }
+
+    /// bookmark the selected log(s), or remove the bookmarks if all of them are bookmarked
+    pub(super) fn toggle_bookmark(&mut self) {
+        let Some((start, end)) = self.selected_display_range_for_action() else {
+            self.set_display_event(
+                "select a log to bookmark".to_string(),
+                self.display_event_duration,
+                None,
+            );
+            return;
+        };
+
+        let ids: Vec<uuid::Uuid> = (start..=end)
+            .filter_map(|i| self.displaying_logs.get(i))
+            .map(|raw_idx| self.raw_logs[raw_idx].id)
+            .collect();
+        let remove = ids.iter().all(|id| self.bookmarks.contains(id));
+        for id in &ids {
+            if remove {
+                self.bookmarks.remove(id);
+            } else {
+                self.bookmarks.insert(*id);
+            }
+        }
+
+        let noun = if ids.len() == 1 { "log" } else { "logs" };
+        let text = if remove {
+            format!("{} {} unbookmarked", ids.len(), noun)
+        } else {
+            format!("{} {} bookmarked", ids.len(), noun)
+        };
+        self.set_display_event(text, self.display_event_duration, None);
+
+        // removed bookmarks drop out of the bookmarks-only view
+        if remove && self.bookmarks_only {
+            self.exit_visual_mode();
+            self.apply_filter();
+        }
+    }
+
+    /// switch between the filtered view and only its bookmarked logs
+    pub(super) fn toggle_bookmarks_only(&mut self) {
+        self.bookmarks_only = !self.bookmarks_only;
+        self.exit_visual_mode();
+        self.apply_filter();
+
+        let text = if self.bookmarks_only {
+            format!("showing {} bookmarked logs", self.displaying_logs.len())
+        } else {
+            "showing all logs".to_string()
+        };
+        self.set_display_event(text, self.display_event_duration, None);
+    }
+
+    /// restrict displayed indices to bookmarked logs while the bookmarks-only view is on
+    pub(super) fn apply_bookmark_view(&self, mut indices: Vec<usize>) -> Vec<usize> {
+        if self.bookmarks_only {
+            indices.retain(|&raw_idx| self.is_bookmarked(raw_idx));
+        }
+        indices
+    }
+
+    /// select the next (or previous) displayed bookmark, wrapping around at the ends
+    pub(super) fn jump_to_bookmark(&mut self, forward: bool) -> Result<()> {
+        if self.bookmarks.is_empty() {
+            self.set_display_event(
+                format!(
+                    "no bookmarks{}",
+                    self.keymap.hint(Action::ToggleBookmark, "add")
+                ),
+                self.display_event_duration,
+                None,
+            );
+            return Ok(());
+        }
+
+        let len = self.displaying_logs.len();
+        let selected = self.displaying_logs.state.selected();
+        let found = (1..=len)
+            .map(|step| match (selected, forward) {
+                (Some(current), true) => (current + step) % len,
+                (Some(current), false) => (current + len - step) % len,
+                (None, true) => step - 1,
+                (None, false) => len - step,
+            })
+            .find(|&i| {
+                self.displaying_logs
+                    .get(i)
+                    .is_some_and(|raw_idx| self.is_bookmarked(raw_idx))
+            });
+
+        let Some(visual_index) = found else {
+            self.set_display_event(
+                "bookmarks are hidden by the filter".to_string(),
+                self.display_event_duration,
+                None,
+            );
+            return Ok(());
+        };
+
+        self.displaying_logs.state.select(Some(visual_index));
+        self.update_selected_uuid();
+        self.after_selection_change()
+    }
+}
@@ -1,12 +1,11 @@
-use super::{App, DISPLAY_EVENT_DURATION_MS, ScrollbarAxis};
-use crate::provider::{decrement_detail_level, increment_detail_level};
+use super::{App, ScrollbarAxis};
+use crate::keymap::{Action, Lookup};
+use crate::provider::{Severity, decrement_detail_level, increment_detail_level};
 use anyhow::Result;
 use arboard::Clipboard;
 use crossterm::event::{
     self, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
 };
-use ratatui::prelude::*;
-use std::time::Duration;
 
 impl App {
     pub(super) fn handle_mouse_event(&mut self, mouse: &MouseEvent) -> Result<()> {
@@ -145,7 +144,7 @@ impl App {
 
         self.set_display_event(
             message,
-            Duration::from_millis(DISPLAY_EVENT_DURATION_MS),
+            self.display_event_duration,
             None, // use default style
         );
 
@@ -180,7 +179,7 @@ impl App {
 
         self.set_display_event(
             format!("{} logs copied to clipboard", yank_contents.len()),
-            Duration::from_millis(DISPLAY_EVENT_DURATION_MS),
+            self.display_event_duration,
             None,
         );
 
@@ -189,6 +188,9 @@ impl App {
 
     pub(super) fn clear_logs(&mut self) {
         self.raw_logs.clear();
+        self.bookmarks.clear();
+        self.raw_logs_bytes = 0;
+        self.overflow.reset_dropped();
         self.filter_engine.reset();
         self.apply_filter();
     }
@@ -200,16 +202,18 @@ impl App {
 
         // help popup mode has higher priority
         if self.show_help_popup {
-            match key.code {
-                KeyCode::Char('?') | KeyCode::Esc => {
-                    self.show_help_popup = false;
-                    return Ok(());
-                }
-                KeyCode::Char('q') => {
-                    // let 'q' fall through to quit the program
-                }
-                _ => return Ok(()), // ignore other keys when help popup is open
+            match self.resolve_key(key) {
+                Some(Action::Help | Action::Back) => self.show_help_popup = false,
+                // quit still quits the program
+                Some(Action::Quit) => self.quit(),
+                _ => {} // ignore other keys when help popup is open
             }
+            return Ok(());
+        }
+
+        // export prompt captures all input until confirmed or cancelled
+        if self.handle_export_prompt_key(key) {
+            return Ok(());
         }
 
         // handle filter input mode when focused
@@ -227,6 +231,10 @@ impl App {
                     self.apply_filter();
                     return Ok(());
                 }
+                KeyCode::Tab => {
+                    self.cycle_filter_mode();
+                    return Ok(());
+                }
                 KeyCode::Backspace => {
                     self.filter_input.pop();
                     // if user deleted the '/', clear the filter and unfocus
@@ -252,134 +260,169 @@ impl App {
             }
         }
 
-        if self.visual_mode {
+        // handle search input mode when focused
+        if self.search_focused {
             match key.code {
-                KeyCode::Char('q') => {
-                    log::debug!("Quit key pressed");
-                    self.provider_stop_signal
-                        .store(true, std::sync::atomic::Ordering::Relaxed);
-                    self.is_exiting = true;
-                    return Ok(());
-                }
-                KeyCode::Char('c') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
-                    self.provider_stop_signal
-                        .store(true, std::sync::atomic::Ordering::Relaxed);
-                    self.is_exiting = true;
+                KeyCode::Esc => {
+                    self.clear_search();
                     return Ok(());
                 }
-                KeyCode::Esc => {
-                    self.exit_visual_mode();
+                KeyCode::Char(c) => {
+                    self.search_input.push(c);
+                    self.apply_search();
                     return Ok(());
                 }
-                KeyCode::Char('j') | KeyCode::Down => {
-                    self.handle_log_item_scrolling(true, true)?;
+                KeyCode::Tab => {
+                    self.cycle_filter_mode();
+                    self.apply_search();
                     return Ok(());
                 }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    self.handle_log_item_scrolling(false, true)?;
+                KeyCode::Backspace => {
+                    // deleting from an empty search leaves search mode
+                    if self.search_input.pop().is_none() {
+                        self.clear_search();
+                    } else {
+                        self.apply_search();
+                    }
                     return Ok(());
                 }
-                KeyCode::Char('y') => {
-                    if let Err(e) = self.yank_current_log() {
-                        log::debug!("Failed to yank selected log content: {}", e);
+                KeyCode::Enter => {
+                    // unfocus the search input and jump to the first match
+                    self.search_focused = false;
+                    if self.search_input.is_empty() {
+                        self.clear_search();
+                    } else {
+                        self.jump_to_match(true)?;
                     }
                     return Ok(());
                 }
-                _ => {
-                    self.set_display_event(
-                        "exit visual mode first".to_string(),
-                        Duration::from_millis(DISPLAY_EVENT_DURATION_MS),
+                _ => {}
+            }
+        }
+
+        // `t` waits for the letter of the level to toggle
+        if self.level_toggle_pending {
+            self.level_toggle_pending = false;
+            // any other key (e.g. Esc) cancels
+            if let KeyCode::Char(c) = key.code {
+                match Severity::from_level(&c.to_string()) {
+                    Some(severity) => self.toggle_level(severity),
+                    None => self.set_display_event(
+                        format!("unknown level '{}'", c),
+                        self.display_event_duration,
                         None,
-                    );
-                    return Ok(());
+                    ),
                 }
             }
+            return Ok(());
+        }
+
+        let Some(action) = self.resolve_key(key) else {
+            return Ok(());
+        };
+        if self.visual_mode {
+            self.perform_visual_action(action)
+        } else {
+            self.perform_action(action)
         }
+    }
+
+    /// the action of the keys pressed so far, `None` while a multi-key binding is incomplete
+    pub(super) fn resolve_key(&mut self, key: KeyEvent) -> Option<Action> {
+        self.pending_keys.push(key.into());
+        match self.keymap.lookup(&self.pending_keys) {
+            Lookup::Action(action) => {
+                self.pending_keys.clear();
+                Some(action)
+            }
+            Lookup::Prefix => None,
+            Lookup::Unbound => {
+                // a key that breaks a sequence (`g` then `j`) counts on its own
+                let retry = self.pending_keys.len() > 1;
+                self.pending_keys.clear();
+                if retry { self.resolve_key(key) } else { None }
+            }
+        }
+    }
+
+    fn quit(&mut self) {
+        log::debug!("Quit key pressed");
+        self.provider_stop_signal
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        self.is_exiting = true;
+    }
+
+    fn perform_visual_action(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::Quit => self.quit(),
+            Action::Back => self.exit_visual_mode(),
+            Action::NextLog => self.handle_log_item_scrolling(true, true)?,
+            Action::PrevLog => self.handle_log_item_scrolling(false, true)?,
+            Action::Yank => {
+                if let Err(e) = self.yank_current_log() {
+                    log::debug!("Failed to yank selected log content: {}", e);
+                }
+            }
+            Action::Export => self.open_export_prompt(),
+            Action::ToggleBookmark => self.toggle_bookmark(),
+            _ => self.set_display_event(
+                "exit visual mode first".to_string(),
+                self.display_event_duration,
+                None,
+            ),
+        }
+        Ok(())
+    }
 
-        match key.code {
-            KeyCode::Char('q') => {
+    fn perform_action(&mut self, action: Action) -> Result<()> {
+        match action {
+            Action::Quit => {
                 // always quit, regardless of filter state or other modes
-                log::debug!("Quit key pressed");
-                self.provider_stop_signal
-                    .store(true, std::sync::atomic::Ordering::Relaxed);
-                self.is_exiting = true;
-                Ok(())
+                self.quit();
             }
-            KeyCode::Esc => {
+            Action::Back => {
                 // Esc only goes back (never quits)
-                // if filter is active but not focused, clear it
-                if !self.filter_input.is_empty() && !self.filter_focused {
+                // clear an active search first, then the filter
+                if self.search_engine.is_active() || self.search_error.is_some() {
+                    self.clear_search();
+                } else if !self.filter_input.is_empty() && !self.filter_focused {
                     self.filter_input.clear();
                     self.apply_filter();
                 }
-                // Esc never quits the program
-                Ok(())
             }
-            KeyCode::Char('v') => {
-                self.enter_visual_mode();
-                Ok(())
-            }
-            KeyCode::Char('c') => {
-                if key.modifiers.contains(event::KeyModifiers::CONTROL) {
-                    self.provider_stop_signal
-                        .store(true, std::sync::atomic::Ordering::Relaxed);
-                    self.is_exiting = true;
-                } else {
-                    self.clear_logs();
-                }
-                Ok(())
-            }
-            KeyCode::Char('j') | KeyCode::Down => {
-                let focused_block = self.get_display_focused_block();
-                if focused_block == self.details_block.id() {
-                    self.handle_details_block_scrolling(true)?;
-                } else if focused_block == self.debug_block.id() {
-                    self.handle_debug_logs_scrolling(true)?;
-                } else {
-                    self.handle_log_item_scrolling(true, true)?;
-                }
-                Ok(())
-            }
-            KeyCode::Char('k') | KeyCode::Up => {
+            Action::VisualMode => self.enter_visual_mode(),
+            Action::Clear => self.clear_logs(),
+            Action::NextLog | Action::PrevLog => {
+                let forward = action == Action::NextLog;
                 let focused_block = self.get_display_focused_block();
                 if focused_block == self.details_block.id() {
-                    self.handle_details_block_scrolling(false)?;
+                    self.handle_details_block_scrolling(forward)?;
                 } else if focused_block == self.debug_block.id() {
-                    self.handle_debug_logs_scrolling(false)?;
+                    self.handle_debug_logs_scrolling(forward)?;
                 } else {
-                    self.handle_log_item_scrolling(false, true)?;
+                    self.handle_log_item_scrolling(forward, true)?;
                 }
-                Ok(())
             }
-            KeyCode::Char('/') | KeyCode::Char('f') => {
-                if self.visual_mode {
-                    return Ok(());
-                }
-
+            Action::Filter => {
                 self.filter_input = "/".to_string();
                 self.filter_focused = true;
                 self.apply_filter();
-                Ok(())
-            }
-            KeyCode::Char('[') => {
-                // decrease detail level (show less info) - non-circular
-                self.detail_level = decrement_detail_level(self.detail_level);
-                // reset filter cache since preview text changes
-                self.filter_engine.reset();
-                self.rebuild_filtered_list();
-                self.update_selection_by_uuid();
-                // notify user of detail level change
-                self.set_display_event(
-                    format!("detail level: {}", self.detail_level),
-                    Duration::from_millis(DISPLAY_EVENT_DURATION_MS),
-                    None,
-                );
-                Ok(())
             }
-            KeyCode::Char(']') => {
-                // increase detail level (show more info) - non-circular
-                let max = self.parser.max_detail_level();
-                self.detail_level = increment_detail_level(self.detail_level, max);
+            Action::Search => {
+                // search keeps every log visible, unlike the filter
+                self.search_input.clear();
+                self.search_focused = true;
+                self.apply_search();
+            }
+            Action::NextMatch => self.jump_to_match(true)?,
+            Action::PrevMatch => self.jump_to_match(false)?,
+            Action::DecreaseDetail | Action::IncreaseDetail => {
+                // change detail level (show less/more info) - non-circular
+                self.detail_level = if action == Action::IncreaseDetail {
+                    increment_detail_level(self.detail_level, self.parser.max_detail_level())
+                } else {
+                    decrement_detail_level(self.detail_level)
+                };
                 // reset filter cache since preview text changes
                 self.filter_engine.reset();
                 self.rebuild_filtered_list();
@@ -387,38 +430,29 @@ impl App {
                 // notify user of detail level change
                 self.set_display_event(
                     format!("detail level: {}", self.detail_level),
-                    Duration::from_millis(DISPLAY_EVENT_DURATION_MS),
+                    self.display_event_duration,
                     None,
                 );
-                Ok(())
             }
-            KeyCode::Char('y') => {
+            Action::Yank => {
                 if let Err(e) = self.yank_current_log() {
                     log::debug!("Failed to yank log content: {}", e);
                 }
-                Ok(())
             }
-            KeyCode::Char('a') => {
+            Action::YankAll => {
                 if let Err(e) = self.yank_all_displayed_logs() {
                     log::debug!("Failed to yank all displayed logs: {}", e);
                 }
-                Ok(())
-            }
-            KeyCode::Char('1') => {
-                self.set_hard_focused_block(self.logs_block.id());
-                Ok(())
-            }
-            KeyCode::Char('2') => {
-                self.set_hard_focused_block(self.details_block.id());
-                Ok(())
             }
-            KeyCode::Char('3') => {
+            Action::Export => self.open_export_prompt(),
+            Action::FocusLogs => self.set_hard_focused_block(self.logs_block.id()),
+            Action::FocusDetails => self.set_hard_focused_block(self.details_block.id()),
+            Action::FocusDebug => {
                 if self.show_debug_logs {
                     self.set_hard_focused_block(self.debug_block.id());
                 }
-                Ok(())
             }
-            KeyCode::Char('w') => {
+            Action::ToggleWrap => {
                 self.text_wrapping_enabled = !self.text_wrapping_enabled;
                 log::debug!("Text wrapping toggled: {}", self.text_wrapping_enabled);
 
@@ -427,15 +461,19 @@ impl App {
                 } else {
                     "text wrapping disabled"
                 };
-                self.set_display_event(
-                    message.to_string(),
-                    Duration::from_millis(DISPLAY_EVENT_DURATION_MS),
-                    None,
-                );
-
-                Ok(())
+                self.set_display_event(message.to_string(), self.display_event_duration, None);
+            }
+            Action::ToggleFolds => {
+                // collapse every nested JSON container, or expand all if any is collapsed
+                if self.details_folds.is_empty() {
+                    self.details_folds
+                        .extend(self.details_fold_paths.iter().cloned());
+                } else {
+                    self.details_folds.clear();
+                }
+                self.details_block.set_scroll_position(0);
             }
-            KeyCode::Char('m') => {
+            Action::ToggleMouseCapture => {
                 let enable = !self.mouse_capture_enabled;
                 self.set_mouse_capture(enable)?;
 
@@ -444,74 +482,34 @@ impl App {
                 } else {
                     "mouse capture disabled - text selection works"
                 };
-                self.set_display_event(
-                    message.to_string(),
-                    Duration::from_millis(DISPLAY_EVENT_DURATION_MS),
-                    None,
-                );
-                Ok(())
+                self.set_display_event(message.to_string(), self.display_event_duration, None);
             }
-            KeyCode::Char('b') => {
+            Action::ToggleDebugLogs => {
                 self.show_debug_logs = !self.show_debug_logs;
                 log::debug!("Debug logs visibility toggled: {}", self.show_debug_logs);
-                Ok(())
-            }
-            KeyCode::Char('h') | KeyCode::Left => {
-                let focused_block = self.get_display_focused_block();
-                self.handle_horizontal_scrolling(focused_block, false)?;
-                Ok(())
             }
-            KeyCode::Char('l') | KeyCode::Right => {
+            Action::ScrollLeft | Action::ScrollRight => {
                 let focused_block = self.get_display_focused_block();
-                self.handle_horizontal_scrolling(focused_block, true)?;
-                Ok(())
-            }
-            KeyCode::Char('?') => {
-                self.show_help_popup = !self.show_help_popup;
-                Ok(())
-            }
-            KeyCode::Char(' ') => {
-                self.after_selection_change()?;
-                Ok(())
-            }
-            KeyCode::Char('d') => {
-                // select the last log item (go to bottom - newest)
-                self.displaying_logs.select_last();
-                self.update_selected_uuid();
-
-                // scroll to bottom (stop when last item is fully displayed)
-                let total_items = self.displaying_logs.len();
-
-                // calculate viewport height to determine max scroll position
-                let viewport_height = if let Some(area) = self.last_logs_area {
-                    let is_focused = self.is_log_block_focused().unwrap_or(false);
-                    let [main_content_area, _] =
-                        Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)])
-                            .margin(0)
-                            .areas(area);
-
-                    let [content_area, _] =
-                        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)])
-                            .margin(0)
-                            .areas(main_content_area);
-
-                    let inner_area =
-                        self.logs_block
-                            .get_content_rect(content_area, is_focused, self.mode_color);
-                    inner_area.height as usize
-                } else {
-                    1 // fallback if area not yet rendered
-                };
-
-                let max_scroll = total_items.saturating_sub(viewport_height);
-                self.logs_block.set_scroll_position(max_scroll);
-                // force autoscroll to be true so that we don't wait for the next render to update the scrollbar state
-                // waiting for the next render may cause new logs arrive beforehand, thus the view is not at the bottom
-                self.update_autoscroll_state();
-                self.update_logs_scrollbar_state();
-                Ok(())
+                self.handle_horizontal_scrolling(focused_block, action == Action::ScrollRight)?;
+            }
+            Action::Help => self.show_help_popup = !self.show_help_popup,
+            Action::CycleMinLevel => self.cycle_min_level(),
+            Action::ToggleLevel => self.level_toggle_pending = true,
+            Action::CycleContext => self.cycle_context(),
+            Action::ToggleBookmark => self.toggle_bookmark(),
+            Action::BookmarksOnly => self.toggle_bookmarks_only(),
+            Action::NextBookmark => self.jump_to_bookmark(true)?,
+            Action::PrevBookmark => self.jump_to_bookmark(false)?,
+            Action::RevealSelection => self.after_selection_change()?,
+            Action::JumpToTop => self.jump_to_top(),
+            Action::JumpToBottom => self.jump_to_bottom(),
+            Action::NextTab | Action::PrevTab | Action::OpenTab | Action::CloseTab => {
+                // handled by the tab host, nothing to do without tabs
+                if self.tabbed {
+                    self.tab_action = Some(action);
+                }
             }
-            _ => Ok(()),
         }
+        Ok(())
     }
 }
@@ -0,0 +1,433 @@
+use super::App;
+use crate::provider::{LogItem, LogParser};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use serde_json::{Map, Value, json};
+use std::{
+    collections::{BTreeSet, HashSet},
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const EXPORT_FILE_STEM: &str = "lazylog-export";
+const EXPORT_RESULT_DURATION_MS: u64 = 2500;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum ExportFormat {
+    /// parser yank content, entries separated by a blank line (same as the clipboard)
+    #[default]
+    Plain,
+    /// raw source text, one entry per line
+    Raw,
+    /// one JSON object per entry with all metadata and the bookmark flag
+    JsonLines,
+    /// time, metadata columns, content and a bookmark column if any entry is bookmarked
+    Csv,
+}
+
+impl ExportFormat {
+    pub(super) fn next(self) -> Self {
+        match self {
+            Self::Plain => Self::Raw,
+            Self::Raw => Self::JsonLines,
+            Self::JsonLines => Self::Csv,
+            Self::Csv => Self::Plain,
+        }
+    }
+
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            Self::Plain => "plain",
+            Self::Raw => "raw",
+            Self::JsonLines => "jsonl",
+            Self::Csv => "csv",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Plain | Self::Raw => "log",
+            Self::JsonLines => "jsonl",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// visual selection to export, by log id as the rows shift if old logs are evicted
+/// while the path is typed
+#[derive(Clone, Copy, Debug)]
+pub(super) struct ExportRange {
+    first: uuid::Uuid,
+    last: uuid::Uuid,
+    /// selected rows when the prompt opened
+    pub(super) len: usize,
+}
+
+/// footer prompt for the export destination
+pub(super) struct ExportPrompt {
+    pub(super) path: String,
+    pub(super) format: ExportFormat,
+    /// `None` exports the whole filtered view
+    pub(super) range: Option<ExportRange>,
+}
+
+impl ExportPrompt {
+    fn new(range: Option<ExportRange>) -> Self {
+        let format = ExportFormat::default();
+        Self {
+            path: format!("{}.{}", EXPORT_FILE_STEM, format.extension()),
+            format,
+            range,
+        }
+    }
+
+    fn cycle_format(&mut self) {
+        let previous = self.format;
+        self.format = previous.next();
+
+        // keep the extension in sync unless the user picked their own
+        if let Some(stem) = self
+            .path
+            .strip_suffix(previous.extension())
+            .and_then(|path| path.strip_suffix('.'))
+        {
+            self.path = format!("{}.{}", stem, self.format.extension());
+        }
+    }
+}
+
+impl App {
+    /// open the export prompt for the visual selection, or the whole filtered view
+    pub(super) fn open_export_prompt(&mut self) {
+        if self.displaying_logs.len() == 0 {
+            self.set_display_event(
+                "nothing to export".to_string(),
+                self.display_event_duration,
+                None,
+            );
+            return;
+        }
+
+        let range = self.visual_selection_range().map(|(start, end)| {
+            let id = |pos: usize| self.raw_logs[self.displaying_logs.indices[pos]].id;
+            ExportRange {
+                first: id(start),
+                last: id(end),
+                len: end - start + 1,
+            }
+        });
+        self.export_prompt = Some(ExportPrompt::new(range));
+    }
+
+    /// returns true if the key was consumed by the export prompt
+    pub(super) fn handle_export_prompt_key(&mut self, key: KeyEvent) -> bool {
+        let Some(prompt) = &mut self.export_prompt else {
+            return false;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.export_prompt = None,
+            KeyCode::Tab => prompt.cycle_format(),
+            KeyCode::Backspace => {
+                prompt.path.pop();
+            }
+            KeyCode::Char(c) => prompt.path.push(c),
+            KeyCode::Enter => {
+                if let Some(prompt) = self.export_prompt.take() {
+                    self.export_logs(&prompt);
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// displayed-log positions of the range to export, `None` if it was evicted
+    fn resolve_export_range(&self, prompt: &ExportPrompt) -> Option<(usize, usize)> {
+        let Some(range) = prompt.range else {
+            return Some((0, self.displaying_logs.len().saturating_sub(1)));
+        };
+        let end = self.find_log_by_uuid(&range.last)?;
+        // an evicted first log leaves the rest of the selection
+        let start = self.find_log_by_uuid(&range.first).unwrap_or(0);
+        Some((start, end))
+    }
+
+    fn export_logs(&mut self, prompt: &ExportPrompt) {
+        let Some((start, end)) = self.resolve_export_range(prompt) else {
+            self.exit_visual_mode();
+            self.set_display_event(
+                "export failed: the selected logs were evicted".to_string(),
+                Duration::from_millis(EXPORT_RESULT_DURATION_MS),
+                None,
+            );
+            return;
+        };
+        let items: Vec<&LogItem> = self
+            .displaying_logs
+            .indices
+            .get(start..=end)
+            .unwrap_or_default()
+            .iter()
+            .map(|&raw_idx| &self.raw_logs[raw_idx])
+            .collect();
+
+        let path = expand_home(prompt.path.trim());
+        let count = items.len();
+        let message = match write_export_file(
+            &path,
+            &items,
+            prompt.format,
+            self.parser.as_ref(),
+            &self.bookmarks,
+        ) {
+            Ok(()) => {
+                log::debug!("Exported {} log items to {}", count, path.display());
+                if prompt.range.is_some() {
+                    self.exit_visual_mode();
+                }
+                format!("{} logs exported to {}", count, path.display())
+            }
+            Err(e) => {
+                log::debug!("Failed to export logs to {}: {}", path.display(), e);
+                format!("export failed: {}", e)
+            }
+        };
+
+        self.set_display_event(
+            message,
+            Duration::from_millis(EXPORT_RESULT_DURATION_MS),
+            None,
+        );
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn write_export_file(
+    path: &Path,
+    items: &[&LogItem],
+    format: ExportFormat,
+    parser: &dyn LogParser,
+    bookmarks: &HashSet<uuid::Uuid>,
+) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_export(&mut writer, items, format, parser, bookmarks)?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub(super) fn write_export(
+    writer: &mut impl Write,
+    items: &[&LogItem],
+    format: ExportFormat,
+    parser: &dyn LogParser,
+    bookmarks: &HashSet<uuid::Uuid>,
+) -> io::Result<()> {
+    match format {
+        ExportFormat::Plain => {
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    writeln!(writer)?;
+                }
+                writeln!(writer, "{}", parser.make_yank_content(item))?;
+            }
+        }
+        ExportFormat::Raw => {
+            for item in items {
+                writeln!(writer, "{}", item.raw_content)?;
+            }
+        }
+        ExportFormat::JsonLines => {
+            for item in items {
+                serde_json::to_writer(
+                    &mut *writer,
+                    &json_line(item, bookmarks.contains(&item.id)),
+                )?;
+                writeln!(writer)?;
+            }
+        }
+        ExportFormat::Csv => {
+            // one column per metadata key seen in the exported items
+            let keys: BTreeSet<&str> = items
+                .iter()
+                .flat_map(|item| item.metadata.keys().map(String::as_str))
+                .collect();
+
+            let has_bookmarks = items.iter().any(|item| bookmarks.contains(&item.id));
+
+            let mut header: Vec<&str> = ["time"]
+                .into_iter()
+                .chain(keys.iter().copied())
+                .chain(["content"])
+                .collect();
+            if has_bookmarks {
+                header.push("bookmarked");
+            }
+            write_csv_row(writer, &header)?;
+
+            for item in items {
+                let mut row: Vec<&str> = [item.time.as_str()]
+                    .into_iter()
+                    .chain(keys.iter().map(|key| item.get_metadata(key).unwrap_or("")))
+                    .chain([item.content.as_str()])
+                    .collect();
+                if has_bookmarks {
+                    row.push(if bookmarks.contains(&item.id) {
+                        "true"
+                    } else {
+                        "false"
+                    });
+                }
+                write_csv_row(writer, &row)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn json_line(item: &LogItem, bookmarked: bool) -> Value {
+    let mut metadata: Vec<_> = item.metadata.iter().collect();
+    metadata.sort();
+    let metadata: Map<String, Value> = metadata
+        .into_iter()
+        .map(|(key, value)| (key.clone(), Value::from(value.as_str())))
+        .collect();
+
+    json!({
+        "id": item.id.to_string(),
+        "time": item.time,
+        "timestamp": item.timestamp.map(|timestamp| timestamp.to_rfc3339()),
+        "content": item.content,
+        "raw_content": item.raw_content,
+        "metadata": metadata,
+        "bookmarked": bookmarked,
+    })
+}
+
+fn write_csv_row(writer: &mut impl Write, fields: &[&str]) -> io::Result<()> {
+    let row = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    // RFC 4180 line endings
+    write!(writer, "{}\r\n", row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::PlainTextParser;
+
+    fn export(items: &[LogItem], format: ExportFormat) -> String {
+        export_with_bookmarks(items, format, &HashSet::new())
+    }
+
+    fn export_with_bookmarks(
+        items: &[LogItem],
+        format: ExportFormat,
+        bookmarks: &HashSet<uuid::Uuid>,
+    ) -> String {
+        let items: Vec<&LogItem> = items.iter().collect();
+        let mut out = Vec::new();
+        write_export(&mut out, &items, format, &PlainTextParser, bookmarks).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_json_lines_include_metadata() {
+        let item = LogItem::new("say \"hi\"\nbye".into(), "raw".into())
+            .with_metadata("level", "ERROR")
+            .with_metadata("tag", "net");
+        let line = export(std::slice::from_ref(&item), ExportFormat::JsonLines);
+
+        let value: serde_json::Value = serde_json::from_str(line.trim_end()).unwrap();
+        assert_eq!(value["content"], "say \"hi\"\nbye");
+        assert_eq!(value["raw_content"], "raw");
+        assert_eq!(value["id"], item.id.to_string());
+        assert!(value["timestamp"].is_null());
+        assert_eq!(value["metadata"]["level"], "ERROR");
+        assert_eq!(value["metadata"]["tag"], "net");
+    }
+
+    #[test]
+    fn test_csv_has_metadata_columns_and_quotes_fields() {
+        let mut first = LogItem::new("a, b".into(), "raw".into()).with_metadata("level", "INFO");
+        first.time = "10:00:00.000".into();
+        let mut second = LogItem::new("say \"hi\"".into(), "raw".into()).with_metadata("tag", "x");
+        second.time = "10:00:01.000".into();
+
+        assert_eq!(
+            export(&[first, second], ExportFormat::Csv),
+            "time,level,tag,content\r\n\
+             10:00:00.000,INFO,,\"a, b\"\r\n\
+             10:00:01.000,,x,\"say \"\"hi\"\"\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_bookmarks_are_exported() {
+        let mut items = vec![
+            LogItem::new("one".into(), "raw one".into()),
+            LogItem::new("two".into(), "raw two".into()),
+        ];
+        items[0].time = "10:00:00.000".into();
+        items[1].time = "10:00:01.000".into();
+        let bookmarks = HashSet::from([items[1].id]);
+
+        let lines = export_with_bookmarks(&items, ExportFormat::JsonLines, &bookmarks);
+        let flags: Vec<bool> = lines
+            .lines()
+            .map(|line| {
+                let value: serde_json::Value = serde_json::from_str(line).unwrap();
+                value["bookmarked"].as_bool().unwrap()
+            })
+            .collect();
+        assert_eq!(flags, vec![false, true]);
+
+        assert_eq!(
+            export_with_bookmarks(&items, ExportFormat::Csv, &bookmarks),
+            "time,content,bookmarked\r\n\
+             10:00:00.000,one,false\r\n\
+             10:00:01.000,two,true\r\n"
+        );
+    }
+
+    #[test]
+    fn test_plain_and_raw_formats() {
+        let items = vec![
+            LogItem::new("one".into(), "raw one".into()),
+            LogItem::new("two".into(), "raw two".into()),
+        ];
+        assert_eq!(export(&items, ExportFormat::Plain), "raw one\n\nraw two\n");
+        assert_eq!(export(&items, ExportFormat::Raw), "raw one\nraw two\n");
+    }
+
+    #[test]
+    fn test_cycling_format_updates_default_extension() {
+        let mut prompt = ExportPrompt::new(None);
+        prompt.cycle_format();
+        assert_eq!(prompt.path, "lazylog-export.log");
+        prompt.cycle_format();
+        assert_eq!(prompt.path, "lazylog-export.jsonl");
+
+        prompt.path = "out.txt".into();
+        prompt.cycle_format();
+        assert_eq!(prompt.format, ExportFormat::Csv);
+        assert_eq!(prompt.path, "out.txt");
+    }
+}
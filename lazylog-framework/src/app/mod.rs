@@ -1,11 +1,17 @@
 use crate::{
     app_block::AppBlock,
-    filter::FilterEngine,
+    filter::{FilterContext, FilterEngine, FilterMode},
+    json_view::{ClickTarget, ContentSegment},
+    keymap::{Action, KeyChord, Keymap},
     log_list::LogList,
     log_parser::{LogDetailLevel, LogItem},
-    provider::{LogParser, LogProvider, spawn_provider_thread},
+    provider::{
+        LogParser, LogProvider, OverflowPolicy, OverflowState, Severity,
+        spawn_provider_thread_with_policy,
+    },
+    search::SearchEngine,
     status_bar::DisplayEvent,
-    theme,
+    theme::Theme,
     ui_logger::UiLogger,
 };
 use anyhow::{Result, anyhow};
@@ -14,24 +20,27 @@ use crossterm::{
     execute,
 };
 use ratatui::{backend::CrosstermBackend, prelude::*, widgets::Widget};
-use ringbuf::{
-    HeapRb,
-    traits::{Consumer, Split},
-};
+use ringbuf::{HeapRb, traits::Split};
 use std::{
+    collections::{HashMap, HashSet},
     io,
     sync::{
-        Arc, Mutex,
+        Arc, Mutex, OnceLock,
         atomic::{AtomicBool, Ordering},
     },
     thread,
     time::{Duration, Instant},
 };
 
+mod bookmarks;
 mod events;
+mod export;
 mod render;
 mod scrolling;
 mod selection;
+mod tabs;
+
+pub use tabs::{TabDesc, start_with_tabs};
 
 // constants
 const DEFAULT_POLL_INTERVAL_MS: u64 = 20;
@@ -39,8 +48,10 @@ const DEFAULT_EVENT_POLL_INTERVAL_MS: u64 = 16;
 const DEFAULT_RING_BUFFER_SIZE: usize = 16384;
 const HELP_POPUP_WIDTH: u16 = 60;
 const SCROLL_PAD: usize = 1;
-const HORIZONTAL_SCROLL_STEP: usize = 5;
-const DISPLAY_EVENT_DURATION_MS: u64 = 800;
+const DEFAULT_HORIZONTAL_SCROLL_STEP: usize = 5;
+const DEFAULT_DISPLAY_EVENT_DURATION_MS: u64 = 800;
+const DEFAULT_DETAIL_LEVEL: LogDetailLevel = 1;
+const RETENTION_HEADROOM_DIVISOR: usize = 10; // evict 10% below the cap so the front isn't shifted on every update
 
 #[derive(Clone)]
 pub struct AppDesc {
@@ -48,10 +59,37 @@ pub struct AppDesc {
     pub event_poll_interval: Duration,
     pub show_debug_logs: bool,
     pub ring_buffer_size: usize,
+    /// what to do when the provider outpaces the UI and the ring buffer is full
+    pub overflow_policy: OverflowPolicy,
+    /// keep at most this many log items, the oldest are evicted first (default: unbounded)
+    pub max_log_items: Option<usize>,
+    /// keep roughly at most this many bytes of log items, the oldest are evicted first
+    /// (default: unbounded)
+    pub max_log_bytes: Option<usize>,
     pub initial_filter: Option<String>,
+    pub filter_mode: FilterMode,
+    /// neighbors shown around filter matches, like grep's `-B`/`-A` (default: none)
+    pub filter_context: FilterContext,
     pub parser: Arc<dyn LogParser>,
     pub mode_name: Option<String>,
     pub mode_color: Option<Color>,
+    /// tag colors of merged sources, logs whose `source` metadata names one of them
+    /// are tagged with it (see [`MultiProvider::source_colors`](crate::MultiProvider::source_colors))
+    pub source_colors: Vec<(String, Color)>,
+    /// columns scrolled per horizontal scroll step
+    pub horizontal_scroll_step: usize,
+    /// how long footer notifications (e.g. "copied") stay visible
+    pub display_event_duration: Duration,
+    /// whether long lines are wrapped on startup (toggled with `w`)
+    pub text_wrapping: bool,
+    /// detail level on startup, capped at the parser's maximum
+    pub detail_level: LogDetailLevel,
+    /// whether mouse events are captured on startup (toggled with `m`)
+    pub mouse_capture: bool,
+    /// key bindings, the help popup lists them
+    pub keymap: Keymap,
+    /// colors and styles of the UI (default: [`Theme::dark`])
+    pub theme: Theme,
 }
 
 impl AppDesc {
@@ -61,10 +99,23 @@ impl AppDesc {
             event_poll_interval: Duration::from_millis(DEFAULT_EVENT_POLL_INTERVAL_MS),
             show_debug_logs: false,
             ring_buffer_size: DEFAULT_RING_BUFFER_SIZE,
+            overflow_policy: OverflowPolicy::default(),
+            max_log_items: None,
+            max_log_bytes: None,
             initial_filter: None,
+            filter_mode: FilterMode::default(),
+            filter_context: FilterContext::default(),
             parser,
             mode_name: None,
             mode_color: None,
+            source_colors: Vec::new(),
+            horizontal_scroll_step: DEFAULT_HORIZONTAL_SCROLL_STEP,
+            display_event_duration: Duration::from_millis(DEFAULT_DISPLAY_EVENT_DURATION_MS),
+            text_wrapping: true,
+            detail_level: DEFAULT_DETAIL_LEVEL,
+            mouse_capture: true,
+            keymap: Keymap::default(),
+            theme: Theme::default(),
         }
     }
 }
@@ -99,20 +150,37 @@ where
 struct App {
     is_exiting: bool,
     raw_logs: Vec<LogItem>,
+    raw_logs_bytes: usize, // Approximate size of raw_logs, see LogItem::approx_size
+    max_log_items: Option<usize>, // Retention cap by item count
+    max_log_bytes: Option<usize>, // Retention cap by approximate size
     displaying_logs: LogList,
     log_consumer: ringbuf::HeapCons<LogItem>, // receives logs from provider thread
     provider_thread: Option<thread::JoinHandle<()>>,
     provider_stop_signal: Arc<AtomicBool>,
+    overflow: Arc<OverflowState>, // Logs dropped by the provider thread because the ring buffer was full
     autoscroll: bool,
     filter_input: String, // Current filter input text (includes leading '/')
     filter_focused: bool, // Whether the filter input is focused
     filter_engine: FilterEngine, // Filtering engine with incremental + parallel support
+    filter_error: Option<String>, // Error from the last filter query (e.g. invalid regex)
+    search_input: String, // Current search input text (without prefix)
+    search_focused: bool, // Whether the search input is focused
+    search_engine: SearchEngine, // Matches of the search query among the displayed logs
+    search_error: Option<String>, // Error from the last search query
+    export_prompt: Option<export::ExportPrompt>, // Destination prompt while exporting logs
+    level_toggle_pending: bool, // `t` was pressed, the next key names the level to toggle
+    bookmarks: HashSet<uuid::Uuid>, // Bookmarked log items, keyed like selection by LogItem::id
+    bookmarks_only: bool, // Whether only bookmarked logs of the filtered view are displayed
     visual_mode: bool,    // Whether visual multi-selection mode is active
     visual_anchor: Option<usize>, // Displayed-log index where visual selection started
     detail_level: LogDetailLevel, // Detail level for log display
+    horizontal_scroll_step: usize, // Columns scrolled per horizontal scroll step
+    display_event_duration: Duration, // How long footer events stay visible
     parser: Arc<dyn LogParser>, // Parser for log items (handles both parsing and formatting)
     mode_name: Option<String>, // Mode name to display in status bar
     mode_color: Color,    // Mode color for borders and status bar
+    source_colors: HashMap<String, Color>, // Tag colors of merged sources, by source name
+    source_tag_width: usize, // Width of the longest source name, tags are padded to it
     debug_logs: Arc<Mutex<Vec<String>>>, // Debug log messages for UI display
     hard_focused_block_id: uuid::Uuid, // Hard focus: set by clicking, persists until another click (defaults to logs_block)
     soft_focused_block_id: Option<uuid::Uuid>, // Soft focus: set by hovering, changes with mouse movement
@@ -129,6 +197,15 @@ struct App {
     mouse_capture_enabled: bool, // Whether mouse events are captured (disable to allow text selection)
     show_debug_logs: bool,       // Whether to show the debug logs block
     show_help_popup: bool,       // Whether to show the help popup
+    tabbed: bool,                // Whether the app is one of several tabs
+    tab_action: Option<Action>,  // Tab action for the tab host, set while handling a key
+    keymap: Keymap,              // Key bindings of the actions
+    details_segments: Vec<ContentSegment>, // Content of the selected log, split into text and JSON payloads
+    details_folds: HashSet<String>, // Paths of the collapsed JSON containers in the details pane
+    details_fold_paths: Vec<String>, // Paths of the nested JSON containers, folded by collapse all
+    details_targets: Vec<Vec<ClickTarget>>, // Click targets of each rendered details line
+    theme: Theme,                   // Colors and styles of every rendered element
+    pending_keys: Vec<KeyChord>,    // Keys typed so far of a multi-key binding (e.g. `g` of `gg`)
     display_event: Option<DisplayEvent>, // Temporary event to display in footer
     prev_hard_focused_block_id: uuid::Uuid, // Track previous hard focus to detect changes
 
@@ -156,15 +233,22 @@ pub(super) enum ScrollbarAxis {
 // Initialization
 // ============================================================================
 impl App {
+    /// the logger is global, every app (e.g. each tab) shares its messages
     fn setup_logger() -> Arc<Mutex<Vec<String>>> {
-        let debug_logs = Arc::new(Mutex::new(Vec::new()));
-        let logger = Box::new(UiLogger::new(debug_logs.clone()));
+        static DEBUG_LOGS: OnceLock<Arc<Mutex<Vec<String>>>> = OnceLock::new();
 
-        if log::set_logger(Box::leak(logger)).is_ok() {
-            log::set_max_level(log::LevelFilter::Debug);
-        }
+        DEBUG_LOGS
+            .get_or_init(|| {
+                let debug_logs = Arc::new(Mutex::new(Vec::new()));
+                let logger = Box::new(UiLogger::new(debug_logs.clone()));
+
+                if log::set_logger(Box::leak(logger)).is_ok() {
+                    log::set_max_level(log::LevelFilter::Debug);
+                }
 
-        debug_logs
+                debug_logs
+            })
+            .clone()
     }
 
     fn new<P>(provider: P, desc: AppDesc) -> Self
@@ -179,8 +263,13 @@ impl App {
 
         // spawn provider thread
         let poll_interval = desc.poll_interval;
-        let (provider_thread, provider_stop_signal) =
-            spawn_provider_thread(provider, desc.parser.clone(), producer, poll_interval);
+        let (provider_thread, provider_stop_signal, overflow) = spawn_provider_thread_with_policy(
+            provider,
+            desc.parser.clone(),
+            producer,
+            poll_interval,
+            desc.overflow_policy,
+        );
 
         // create blocks first so we can reference their IDs
         let logs_block = AppBlock::new().set_title("[1]─Logs".to_string());
@@ -196,6 +285,11 @@ impl App {
         // setup filter engine with parser
         let mut filter_engine = FilterEngine::new();
         filter_engine.set_formatter(desc.parser.clone());
+        filter_engine.set_mode(desc.filter_mode);
+        filter_engine.set_context(desc.filter_context);
+
+        let mut search_engine = SearchEngine::new();
+        search_engine.set_formatter(desc.parser.clone());
 
         let initial_filter_input = desc
             .initial_filter
@@ -208,25 +302,48 @@ impl App {
         let mode_name = desc.mode_name.clone();
         let mode_color = desc
             .mode_color
-            .unwrap_or_else(|| theme::get_mode_color(&mode_name));
+            .unwrap_or_else(|| desc.theme.mode_color(&mode_name));
+        let source_tag_width = desc
+            .source_colors
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0);
 
         Self {
             is_exiting: false,
             raw_logs: Vec::new(),
+            raw_logs_bytes: 0,
+            max_log_items: desc.max_log_items,
+            max_log_bytes: desc.max_log_bytes,
             displaying_logs: LogList::new(Vec::new()),
             log_consumer: consumer,
             provider_thread: Some(provider_thread),
             provider_stop_signal,
+            overflow,
             autoscroll: true,
             filter_input: initial_filter_input,
             filter_focused: false,
             filter_engine,
+            filter_error: None,
+            search_input: String::new(),
+            search_focused: false,
+            search_engine,
+            search_error: None,
+            export_prompt: None,
+            level_toggle_pending: false,
+            bookmarks: HashSet::new(),
+            bookmarks_only: false,
             visual_mode: false,
             visual_anchor: None,
-            detail_level: 1, // default detail level (was Basic)
+            detail_level: desc.detail_level.min(desc.parser.max_detail_level()),
+            horizontal_scroll_step: desc.horizontal_scroll_step,
+            display_event_duration: desc.display_event_duration,
             parser: desc.parser,
             mode_name,
             mode_color,
+            source_colors: desc.source_colors.into_iter().collect(),
+            source_tag_width,
             debug_logs,
             hard_focused_block_id: logs_block_id,
             soft_focused_block_id: None,
@@ -239,10 +356,19 @@ impl App {
             last_details_area: None,
             last_debug_area: None,
             last_logs_viewport_height: None,
-            text_wrapping_enabled: true,
-            mouse_capture_enabled: true,
+            text_wrapping_enabled: desc.text_wrapping,
+            mouse_capture_enabled: true, // the terminal is set up with mouse capture
             show_debug_logs: desc.show_debug_logs,
             show_help_popup: false,
+            tabbed: false,
+            tab_action: None,
+            keymap: desc.keymap,
+            details_segments: Vec::new(),
+            details_folds: HashSet::new(),
+            details_fold_paths: Vec::new(),
+            details_targets: Vec::new(),
+            theme: desc.theme,
+            pending_keys: Vec::new(),
             display_event: None,
             prev_hard_focused_block_id: logs_block_id,
 
@@ -268,6 +394,7 @@ impl App {
         let poll_interval = desc.poll_interval;
         let event_poll_interval = desc.event_poll_interval;
         let mut last_update_logs = Instant::now();
+        self.set_mouse_capture(desc.mouse_capture)?;
 
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| -> Result<()> {
             while !self.is_exiting {
@@ -311,20 +438,23 @@ impl App {
 
     fn poll_event(&mut self, poll_interval: Duration) -> Result<()> {
         if event::poll(poll_interval)? {
-            let event = event::read()?;
-            match event {
-                Event::Key(key) => self.handle_key(key)?,
-                Event::Mouse(mouse) => {
-                    if self.mouse_capture_enabled {
-                        self.handle_mouse_event(&mouse)?;
-                        self.mouse_event = Some(mouse);
-                    }
-                }
-                Event::Resize(width, height) => {
-                    log::debug!("Terminal resized to {}x{}", width, height);
-                }
-                _ => {}
+            self.handle_event(event::read()?)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<()> {
+        match event {
+            Event::Key(key) => self.handle_key(key)?,
+            Event::Mouse(mouse) if self.mouse_capture_enabled => {
+                self.handle_mouse_event(&mouse)?;
+                self.mouse_event = Some(mouse);
+            }
+            Event::Resize(width, height) => {
+                log::debug!("Terminal resized to {}x{}", width, height);
             }
+            _ => {}
         }
 
         Ok(())
@@ -352,33 +482,49 @@ impl App {
 // Log and filter management
 // ============================================================================
 impl App {
-    fn update_logs(&mut self) -> Result<()> {
+    /// ingest the logs waiting in the ring buffer, returns how many arrived
+    fn update_logs(&mut self) -> Result<usize> {
         // consume all available logs from ring buffer
-        let mut new_logs = Vec::new();
-        while let Some(log) = self.log_consumer.try_pop() {
-            new_logs.push(log);
-        }
+        let new_logs = self.overflow.drain(&mut self.log_consumer);
 
         if new_logs.is_empty() {
-            return Ok(());
+            return Ok(0);
         }
+        let received = new_logs.len();
 
         let previous_uuid = self.selected_log_uuid;
         let previous_scroll_pos = Some(self.logs_block.get_scroll_position());
 
         log::debug!("Received {} new log items from provider", new_logs.len());
         let old_raw_count = self.raw_logs.len();
+        self.raw_logs_bytes += new_logs.iter().map(LogItem::approx_size).sum::<usize>();
         self.raw_logs.extend(new_logs);
+        let evicted = self.enforce_retention();
+        let old_raw_count = old_raw_count.saturating_sub(evicted);
 
-        // use incremental filtering for efficiency (only filters new logs)
-        let filter_query = self.get_filter_query().to_string();
-        let filtered_indices = self.filter_engine.filter_new_logs(
+        // use incremental filtering for efficiency (only filters new logs);
+        // while the typed query is invalid, keep following the last valid one
+        let filter_query = if self.filter_error.is_some() {
+            self.filter_engine.active_query().to_string()
+        } else {
+            self.get_filter_query().to_string()
+        };
+        match self.filter_engine.filter_new_logs(
             &self.raw_logs,
             old_raw_count,
             &filter_query,
             self.detail_level,
+        ) {
+            Ok(filtered_indices) => {
+                self.displaying_logs = LogList::new(self.apply_bookmark_view(filtered_indices))
+            }
+            Err(e) => self.filter_error = Some(e.to_string()),
+        }
+        self.search_engine.update(
+            &self.raw_logs,
+            &self.displaying_logs.indices,
+            self.detail_level,
         );
-        self.displaying_logs = LogList::new(filtered_indices);
 
         if previous_uuid.is_some() {
             self.update_selection_by_uuid();
@@ -401,9 +547,12 @@ impl App {
                             .margin(0)
                             .areas(main_content_area);
 
-                    let inner_area =
-                        self.logs_block
-                            .get_content_rect(content_area, is_focused, self.mode_color);
+                    let inner_area = self.logs_block.get_content_rect(
+                        content_area,
+                        &self.theme,
+                        is_focused,
+                        self.mode_color,
+                    );
                     inner_area.height as usize
                 } else {
                     1 // fallback if area not yet rendered
@@ -425,7 +574,69 @@ impl App {
             );
         }
 
-        Ok(())
+        Ok(received)
+    }
+
+    /// evict the oldest logs once a retention cap is exceeded, returns how many were evicted
+    ///
+    /// raw indices in the filter cache and the displayed list are shifted, so selection,
+    /// visual anchor and scroll position keep pointing at the same items
+    fn enforce_retention(&mut self) -> usize {
+        let over_items = self
+            .max_log_items
+            .is_some_and(|max| self.raw_logs.len() > max);
+        let over_bytes = self
+            .max_log_bytes
+            .is_some_and(|max| self.raw_logs_bytes > max);
+        if !over_items && !over_bytes {
+            return 0;
+        }
+
+        let target_items = self
+            .max_log_items
+            .map(|max| max - max / RETENTION_HEADROOM_DIVISOR);
+        let target_bytes = self
+            .max_log_bytes
+            .map(|max| max - max / RETENTION_HEADROOM_DIVISOR);
+
+        let total = self.raw_logs.len();
+        let mut count = 0;
+        let mut bytes = self.raw_logs_bytes;
+        for item in &self.raw_logs {
+            let items_ok = target_items.is_none_or(|target| total - count <= target);
+            let bytes_ok = target_bytes.is_none_or(|target| bytes <= target);
+            if items_ok && bytes_ok {
+                break;
+            }
+            bytes = bytes.saturating_sub(item.approx_size());
+            count += 1;
+        }
+
+        for item in self.raw_logs.drain(..count) {
+            self.bookmarks.remove(&item.id);
+        }
+        self.raw_logs_bytes = bytes;
+        self.filter_engine.evict_front(count);
+        self.search_engine.evict_front(count);
+
+        let removed_rows = self.displaying_logs.evict_front(count);
+        self.visual_anchor = self
+            .visual_anchor
+            .map(|anchor| anchor.saturating_sub(removed_rows));
+        if !self.autoscroll {
+            let pos = self.logs_block.get_scroll_position();
+            self.logs_block
+                .set_scroll_position(pos.saturating_sub(removed_rows));
+        }
+
+        // the selected item itself was evicted
+        if self.displaying_logs.state.selected().is_none() && self.selected_log_uuid.is_some() {
+            self.selected_log_uuid = None;
+            self.exit_visual_mode();
+        }
+
+        log::debug!("Evicted {} oldest log items", count);
+        count
     }
 
     fn get_filter_query(&self) -> &str {
@@ -491,11 +702,163 @@ impl App {
         let filter_query = self.get_filter_query().to_string();
 
         // use FilterEngine for filtering (incremental + parallel)
-        let filtered_indices =
-            self.filter_engine
-                .filter(&self.raw_logs, &filter_query, self.detail_level);
+        match self
+            .filter_engine
+            .filter(&self.raw_logs, &filter_query, self.detail_level)
+        {
+            Ok(filtered_indices) => {
+                self.filter_error = None;
+                self.displaying_logs = LogList::new(self.apply_bookmark_view(filtered_indices));
+            }
+            Err(e) => {
+                // keep showing the results of the last valid query
+                self.filter_error = Some(e.to_string());
+            }
+        }
+
+        // the displayed logs changed, search them again
+        self.search_engine.refresh(
+            &self.raw_logs,
+            &self.displaying_logs.indices,
+            self.detail_level,
+        );
+    }
+
+    fn apply_search(&mut self) {
+        match self
+            .search_engine
+            .set_query(&self.search_input, self.filter_engine.mode())
+        {
+            Ok(()) => {
+                self.search_error = None;
+                self.search_engine.refresh(
+                    &self.raw_logs,
+                    &self.displaying_logs.indices,
+                    self.detail_level,
+                );
+            }
+            // keep the matches of the last valid query
+            Err(e) => self.search_error = Some(e.to_string()),
+        }
+    }
+
+    fn clear_search(&mut self) {
+        self.search_focused = false;
+        self.search_input.clear();
+        self.search_error = None;
+        self.search_engine.clear();
+    }
+
+    /// select the next (or previous) search match, wrapping around at the ends
+    fn jump_to_match(&mut self, forward: bool) -> Result<()> {
+        if !self.search_engine.is_active() {
+            self.set_display_event(
+                "no active search (s to search)".to_string(),
+                self.display_event_duration,
+                None,
+            );
+            return Ok(());
+        }
+
+        let from = self
+            .displaying_logs
+            .state
+            .selected()
+            .and_then(|i| self.displaying_logs.get(i));
+        let Some((raw_idx, wrapped)) = self.search_engine.next_match(from, forward) else {
+            self.set_display_event(
+                format!("no matches for '{}'", self.search_input),
+                self.display_event_duration,
+                None,
+            );
+            return Ok(());
+        };
+
+        // matches are a subset of the displayed logs
+        if let Ok(visual_index) = self.displaying_logs.indices.binary_search(&raw_idx) {
+            self.displaying_logs.state.select(Some(visual_index));
+            self.update_selected_uuid();
+            self.after_selection_change()?;
+        }
+
+        if wrapped {
+            let text = if forward {
+                "search wrapped to top"
+            } else {
+                "search wrapped to bottom"
+            };
+            self.set_display_event(text.to_string(), self.display_event_duration, None);
+        }
+        Ok(())
+    }
+
+    /// footer text for the search position, e.g. "match 3 of 12"
+    fn search_status(&self) -> String {
+        let total = self.search_engine.matches().len();
+        if total == 0 {
+            return "no matches".to_string();
+        }
+
+        let current = self
+            .displaying_logs
+            .state
+            .selected()
+            .and_then(|i| self.displaying_logs.get(i))
+            .and_then(|raw_idx| self.search_engine.position(raw_idx));
+        match current {
+            Some(position) => format!("match {} of {}", position + 1, total),
+            None => format!("{} matches", total),
+        }
+    }
+
+    fn cycle_filter_mode(&mut self) {
+        let mode = self.filter_engine.mode().next();
+        self.filter_engine.set_mode(mode);
+        self.apply_filter();
+        self.set_display_event(
+            format!("Filter mode: {}", mode.label()),
+            self.display_event_duration,
+            None,
+        );
+    }
+
+    fn cycle_min_level(&mut self) {
+        let mut levels = self.filter_engine.levels().clone();
+        levels.cycle_min_level();
+        let text = match levels.min_level() {
+            Some(min) => format!("Minimum level: {}", min.label()),
+            None => "Minimum level: off".to_string(),
+        };
+
+        self.filter_engine.set_levels(levels);
+        self.apply_filter();
+        self.set_display_event(text, self.display_event_duration, None);
+    }
+
+    fn toggle_level(&mut self, severity: Severity) {
+        let mut levels = self.filter_engine.levels().clone();
+        let hidden = levels.toggle(severity);
+        let text = format!(
+            "{} logs {}",
+            severity.label(),
+            if hidden { "hidden" } else { "shown" }
+        );
+
+        self.filter_engine.set_levels(levels);
+        self.apply_filter();
+        self.set_display_event(text, self.display_event_duration, None);
+    }
+
+    fn cycle_context(&mut self) {
+        let context = self.filter_engine.context().cycle();
+        let text = match context.indicator() {
+            Some(indicator) => indicator,
+            None => "context off".to_string(),
+        };
 
-        self.displaying_logs = LogList::new(filtered_indices);
+        self.filter_engine.set_context(context);
+        self.apply_filter();
+        self.set_display_event(text, self.display_event_duration, None);
     }
 
     fn update_logs_scrollbar_state(&mut self) {
@@ -676,7 +1039,7 @@ impl App {
             text,
             duration,
             style,
-            theme::DISPLAY_EVENT_STYLE,
+            self.theme.display_event,
         ));
     }
 
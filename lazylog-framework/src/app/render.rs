@@ -1,8 +1,14 @@
 use super::{App, HELP_POPUP_WIDTH, ScrollableBlockType};
 use crate::{
     app_block::AppBlock,
-    content_line_maker::{WrappingMode, calculate_content_width, content_into_lines},
-    theme,
+    content_line_maker::{WrappingMode, content_into_lines},
+    filter::FilterQuery,
+    json_view::{
+        ClickAction, ClickTarget, ContentSegment, DetailsLine, json_lines, nested_fold_paths,
+        split_json,
+    },
+    keymap::{Action, HELP_SECTIONS, HelpKeys},
+    provider::SOURCE_METADATA_KEY,
 };
 use anyhow::Result;
 use arboard::Clipboard;
@@ -11,38 +17,53 @@ use ratatui::{
     prelude::*,
     widgets::{Paragraph, StatefulWidget, Widget},
 };
-use std::time::{Duration, Instant};
-use unicode_width::UnicodeWidthChar;
-
-/// helper function to highlight filter matches in text
-/// splits text into spans, applying bold & underlined style to matching parts
-fn create_highlighted_line(text: &str, filter_query: &str, base_style: Style) -> Line<'static> {
-    if filter_query.is_empty() {
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// helper function to highlight filter and search matches in text
+/// splits text into spans, patching the highlight style onto matching parts
+fn create_highlighted_line(
+    text: &str,
+    queries: &[&FilterQuery],
+    base_style: Style,
+    highlight_style: Style,
+) -> Line<'static> {
+    if queries.is_empty() {
         return Line::styled(text.to_string(), base_style);
     }
 
-    let text_lower = text.to_lowercase();
-    let query_lower = filter_query.to_lowercase();
+    // ranges of different queries may overlap
+    let mut ranges: Vec<(usize, usize)> = queries
+        .iter()
+        .flat_map(|query| query.highlight_ranges(text))
+        .collect();
+    ranges.sort_unstable();
+
     let mut spans = Vec::new();
     let mut last_pos = 0;
 
-    // find all occurrences of the filter query
-    while let Some(match_pos) = text_lower[last_pos..].find(&query_lower) {
-        let absolute_pos = last_pos + match_pos;
+    // find all occurrences of the queries
+    for (match_start, match_end) in ranges {
+        let match_start = match_start.max(last_pos);
+        if match_start >= match_end {
+            continue;
+        }
 
         // add non-matching part before the match
-        if last_pos < absolute_pos {
+        if last_pos < match_start {
             spans.push(Span::styled(
-                text[last_pos..absolute_pos].to_string(),
+                text[last_pos..match_start].to_string(),
                 base_style,
             ));
         }
 
-        // add matching part with bold & underlined
-        let match_end = absolute_pos + filter_query.len();
+        // add matching part with the highlight style on top
         spans.push(Span::styled(
-            text[absolute_pos..match_end].to_string(),
-            base_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            text[match_start..match_end].to_string(),
+            base_style.patch(highlight_style),
         ));
 
         last_pos = match_end;
@@ -56,6 +77,39 @@ fn create_highlighted_line(text: &str, filter_query: &str, base_style: Style) ->
     Line::from(spans)
 }
 
+/// patches `style` onto the byte range of the line's text, splitting spans at its ends
+fn patch_line_range(line: Line<'static>, range: Range<usize>, style: Style) -> Line<'static> {
+    let line_style = line.style;
+    let mut spans = Vec::with_capacity(line.spans.len() + 2);
+    let mut span_start = 0;
+
+    for span in line.spans {
+        let text = span.content.as_ref();
+        let span_end = span_start + text.len();
+        let start = range.start.clamp(span_start, span_end) - span_start;
+        let end = range.end.clamp(span_start, span_end) - span_start;
+        span_start = span_end;
+
+        if start >= end || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+            spans.push(span);
+            continue;
+        }
+
+        if start > 0 {
+            spans.push(Span::styled(text[..start].to_string(), span.style));
+        }
+        spans.push(Span::styled(
+            text[start..end].to_string(),
+            span.style.patch(style),
+        ));
+        if end < text.len() {
+            spans.push(Span::styled(text[end..].to_string(), span.style));
+        }
+    }
+
+    Line::from(spans).style(line_style)
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum WordClass {
     AlphaNum,
@@ -139,9 +193,38 @@ impl App {
             );
         }
         status_bar = status_bar.add_status_plain(StatusGravity::Left, wrap_text);
+        if !self.filter_input.is_empty() {
+            let filter_mode_text = format!("filter {}", self.filter_engine.mode().label());
+            status_bar = status_bar.add_status_plain(StatusGravity::Left, &filter_mode_text);
+        }
+        if let Some(levels_text) = self.filter_engine.levels().indicator() {
+            status_bar = status_bar.add_status_plain(StatusGravity::Left, &levels_text);
+        }
+        if self.bookmarks_only {
+            status_bar = status_bar.add_status_plain(StatusGravity::Left, "bookmarks only");
+        }
+        if let Some(context_text) = self.filter_engine.context().indicator() {
+            status_bar = status_bar.add_status_plain(StatusGravity::Left, &context_text);
+        }
         status_bar = status_bar.add_status_plain(StatusGravity::Right, &version_text);
 
-        if let Some(event) = &self.display_event {
+        if let Some(prompt) = &self.export_prompt {
+            let target = match prompt.range {
+                Some(range) => format!("{} selected", range.len),
+                None => format!("{} displayed", self.displaying_logs.len()),
+            };
+            let text = format!(
+                "export {} as {} to: {}_ | Tab format | Enter save | Esc cancel",
+                target,
+                prompt.format.label(),
+                prompt.path
+            );
+            status_bar = status_bar.add_status(
+                StatusGravity::Mid,
+                text,
+                StatusStyle::from_colors(self.theme.prompt.fg, self.theme.prompt.bg),
+            );
+        } else if let Some(event) = &self.display_event {
             let elapsed = event.start_time.elapsed().as_millis() as f32;
             let duration = event.duration.as_millis() as f32;
             let progress = elapsed / duration;
@@ -153,34 +236,74 @@ impl App {
                     1.0 - ((progress - 0.8) / 0.2)
                 };
                 let fg = if alpha_factor < 0.5 {
-                    Color::Gray
+                    Some(self.theme.display_event_fade)
                 } else {
-                    Color::Black
+                    event.style.fg
                 };
-                StatusStyle::from_colors(Some(fg), Some(Color::Yellow))
+                StatusStyle::from_colors(fg, event.style.bg)
             } else {
-                StatusStyle::from_colors(Some(Color::Black), Some(Color::Yellow))
+                StatusStyle::from_colors(event.style.fg, event.style.bg)
             };
 
             let padded_text = format!(" {} ", event.text);
             status_bar = status_bar.add_status(StatusGravity::Mid, padded_text, mid_style);
+        } else if self.level_toggle_pending {
+            status_bar = status_bar.add_status_plain(
+                StatusGravity::Mid,
+                "toggle level: v d i n w e f | Esc cancel",
+            );
         } else if self.visual_mode {
-            let visual_hint = "VISUAL: j/k select | y copy | Esc exit";
+            let visual_hint = "VISUAL: j/k select | y copy | e export | x bookmark | Esc exit";
             if self.filter_input.is_empty() {
                 status_bar = status_bar.add_status_plain(StatusGravity::Mid, visual_hint);
             } else {
                 let text = format!("{} | {}", self.filter_input, visual_hint);
                 status_bar = status_bar.add_status_plain(StatusGravity::Mid, &text);
             }
+        } else if let Some(error) = &self.filter_error {
+            let text = format!("{} | {}", self.filter_input, error);
+            status_bar = status_bar.add_status(
+                StatusGravity::Mid,
+                text,
+                StatusStyle::from_colors(self.theme.status_error.fg, self.theme.status_error.bg),
+            );
+        } else if let Some(error) = &self.search_error {
+            let text = format!("search: {} | {}", self.search_input, error);
+            status_bar = status_bar.add_status(
+                StatusGravity::Mid,
+                text,
+                StatusStyle::from_colors(self.theme.status_error.fg, self.theme.status_error.bg),
+            );
+        } else if self.search_focused || self.search_engine.is_active() {
+            let mut text = String::new();
+            if !self.filter_input.is_empty() {
+                text = format!("{} | ", self.filter_input);
+            }
+            text += &format!("search: {}", self.search_input);
+            if self.search_engine.is_active() {
+                text += &format!(" | {}", self.search_status());
+            }
+            status_bar = status_bar.add_status_plain(StatusGravity::Mid, &text);
         } else if !self.filter_input.is_empty() {
             status_bar = status_bar.add_status_plain(StatusGravity::Mid, &self.filter_input);
         } else {
-            status_bar = status_bar.add_status_plain(StatusGravity::Mid, "?: help | q: quit");
+            // first key of each action, as remapped in the keymap
+            let hints: Vec<String> = [(Action::Help, "help"), (Action::Quit, "quit")]
+                .into_iter()
+                .filter_map(|(action, what)| {
+                    let key = self.keymap.keys(action).next()?;
+                    Some(format!("{}: {}", key, what))
+                })
+                .collect();
+            status_bar = status_bar.add_status_plain(StatusGravity::Mid, &hints.join(" | "));
         }
 
-        if self.filter_focused && self.display_event.is_none() {
-            status_bar = status_bar.set_style(theme::FILTER_FOCUS_STYLE);
-        }
+        status_bar = if (self.filter_focused || self.search_focused) && self.display_event.is_none()
+        {
+            status_bar.set_style(self.theme.status_bar.patch(self.theme.filter_focus))
+        } else {
+            status_bar.set_style(self.theme.status_bar)
+        };
 
         status_bar.render(area, buf);
         Ok(())
@@ -189,29 +312,58 @@ impl App {
     pub(super) fn render_help_popup(&self, area: Rect, buf: &mut Buffer) -> Result<()> {
         use ratatui::widgets::{Block, Borders, Clear};
 
-        let help_text = vec![
-            Line::from("Navigation:".bold()),
-            Line::from("  j/k/↑/↓  - Move to prev/next log"),
-            Line::from("  d        - Jump to bottom (latest log)"),
-            Line::from("  space    - Make selected log visible in view"),
-            Line::from(""),
-            Line::from("Actions:".bold()),
-            Line::from("  / or f   - Enter filter mode"),
-            Line::from("  v        - Enter visual mode"),
-            Line::from("  y        - Copy selected log(s) to clipboard"),
-            Line::from("  a        - Copy all displayed logs to clipboard"),
-            Line::from("  c        - Clear all logs"),
-            Line::from("  w        - Toggle text wrapping"),
-            Line::from("  m        - Toggle mouse capture (select text when off)"),
-            Line::from("  [        - Decrease detail level"),
-            Line::from("  ]        - Increase detail level"),
-            Line::from("  Esc      - Exit visual / go back / clear filter"),
-            Line::from("  q        - Quit program"),
-            Line::from(""),
-            Line::from("Focus:".bold()),
-            Line::from("  <num_key>    - Toggle focus on panel"),
-            Line::from("  Shift+scroll - Horizontal scroll with mouse"),
-        ];
+        // generated from the keymap so it always shows the real bindings
+        let mut sections = Vec::new();
+        for section in HELP_SECTIONS {
+            if section.tabs_only && !self.tabbed {
+                continue;
+            }
+            let entries: Vec<(String, &str)> = section
+                .entries
+                .iter()
+                .map(|(keys, description)| {
+                    let keys = match keys {
+                        HelpKeys::Bound(actions) => actions
+                            .iter()
+                            .map(|&action| self.keymap.describe(action))
+                            .filter(|keys| !keys.is_empty())
+                            .collect::<Vec<_>>()
+                            .join("/"),
+                        HelpKeys::Fixed(keys) => keys.to_string(),
+                    };
+                    (keys, *description)
+                })
+                // unbound actions are left out
+                .filter(|(keys, _)| !keys.is_empty())
+                .collect();
+            sections.push((section.title, entries));
+        }
+
+        let keys_width = sections
+            .iter()
+            .flat_map(|(_, entries)| entries.iter().map(|(keys, _)| keys.width()))
+            .max()
+            .unwrap_or(0);
+        let mut help_text = Vec::new();
+        for (title, entries) in sections {
+            if !help_text.is_empty() {
+                help_text.push(Line::from(""));
+            }
+            help_text.push(Line::from(title.bold()));
+            for (keys, description) in entries {
+                let padding = " ".repeat(keys_width - keys.width());
+                help_text.push(Line::from(format!(
+                    "  {}{} - {}",
+                    keys, padding, description
+                )));
+            }
+        }
+        let popup_width = help_text
+            .iter()
+            .map(|line| line.width() as u16 + 2)
+            .max()
+            .unwrap_or(0)
+            .max(HELP_POPUP_WIDTH);
 
         // calculate popup height: content lines + 2 for borders
         let popup_height = help_text.len() as u16 + 2;
@@ -226,7 +378,7 @@ impl App {
 
         let popup_area = Layout::horizontal([
             Constraint::Fill(1),
-            Constraint::Length(HELP_POPUP_WIDTH),
+            Constraint::Length(popup_width),
             Constraint::Fill(1),
         ])
         .split(popup_area)[1];
@@ -241,7 +393,7 @@ impl App {
 
         Paragraph::new(help_text)
             .block(block)
-            .fg(theme::TEXT_FG_COLOR)
+            .fg(self.theme.text)
             .render(popup_area, buf);
 
         Ok(())
@@ -280,7 +432,9 @@ impl App {
                 ScrollableBlockType::Details => &self.details_block,
                 ScrollableBlockType::Debug => &self.debug_block,
             };
-            let inner_area = block_ref.build(false, self.mode_color).inner(area);
+            let inner_area = block_ref
+                .build(&self.theme, false, self.mode_color)
+                .inner(area);
             let is_within_bounds =
                 inner_area.contains(ratatui::layout::Position::new(event.column, event.row));
 
@@ -312,8 +466,12 @@ impl App {
             ScrollableBlockType::Details => &self.details_block,
             ScrollableBlockType::Debug => &self.debug_block,
         };
-        let temp_content_rect =
-            block_ref.get_content_rect(vertical_content_area, is_focused, self.mode_color);
+        let temp_content_rect = block_ref.get_content_rect(
+            vertical_content_area,
+            &self.theme,
+            is_focused,
+            self.mode_color,
+        );
         let needs_horizontal_scrollbar = max_content_width > temp_content_rect.width as usize;
 
         // create vertical layout for content and horizontal scrollbar
@@ -331,7 +489,8 @@ impl App {
                 ScrollableBlockType::Debug => &mut self.debug_block,
             };
 
-            let content_rect = block.get_content_rect(content_area, is_focused, self.mode_color);
+            let content_rect =
+                block.get_content_rect(content_area, &self.theme, is_focused, self.mode_color);
             block.update_horizontal_scrollbar_state(max_content_width, content_rect.width as usize);
             block.set_lines_count(lines_count);
             let scroll_position = block.get_scroll_position();
@@ -363,18 +522,18 @@ impl App {
                 ScrollableBlockType::Details => &self.details_block,
                 ScrollableBlockType::Debug => &self.debug_block,
             };
-            block.build(is_focused, self.mode_color)
+            block.build(&self.theme, is_focused, self.mode_color)
         };
 
         // render paragraph with scrolling
         Paragraph::new(content)
             .block(block_widget)
-            .fg(theme::TEXT_FG_COLOR)
+            .fg(self.theme.text)
             .scroll((scroll_position as u16, h_scroll))
             .render(content_area, buf);
 
         // render vertical scrollbar
-        let scrollbar = AppBlock::create_scrollbar(is_focused, self.mode_color);
+        let scrollbar = AppBlock::create_scrollbar(&self.theme, is_focused, self.mode_color);
         let block_ref = match block_type {
             ScrollableBlockType::Details => &mut self.details_block,
             ScrollableBlockType::Debug => &mut self.debug_block,
@@ -388,9 +547,9 @@ impl App {
 
         // render horizontal scrollbar (track-only when not needed)
         let horizontal_scrollbar = if needs_horizontal_scrollbar {
-            AppBlock::create_horizontal_scrollbar(is_focused, self.mode_color)
+            AppBlock::create_horizontal_scrollbar(&self.theme, is_focused, self.mode_color)
         } else {
-            AppBlock::create_horizontal_track_only(is_focused, self.mode_color)
+            AppBlock::create_horizontal_track_only(&self.theme, is_focused, self.mode_color)
         };
         StatefulWidget::render(
             horizontal_scrollbar,
@@ -417,9 +576,17 @@ impl App {
 
         let is_log_focused = self.is_log_block_focused().unwrap_or(false);
 
-        // clone filter_query early to avoid borrow checker issues
+        // clone filter_query and parsed queries early to avoid borrow checker issues
         let filter_query = self.get_filter_query().to_string();
-        let mut title = if filter_query.is_empty() {
+        let highlight_queries: Vec<FilterQuery> = self
+            .filter_engine
+            .query()
+            .into_iter()
+            .chain(self.search_engine.query())
+            .cloned()
+            .collect();
+        let highlight_queries: Vec<&FilterQuery> = highlight_queries.iter().collect();
+        let mut title = if filter_query.is_empty() && !self.bookmarks_only {
             format!("[1]─Logs - {}", self.raw_logs.len())
         } else {
             format!(
@@ -429,6 +596,15 @@ impl App {
             )
         };
 
+        if !self.bookmarks.is_empty() {
+            title += &format!(" - {} bookmarked", self.bookmarks.len());
+        }
+
+        let dropped = self.overflow.dropped();
+        if dropped > 0 {
+            title += &format!(" - {} dropped", dropped);
+        }
+
         self.update_autoscroll_state();
 
         if self.autoscroll {
@@ -442,9 +618,12 @@ impl App {
         let total_lines = self.displaying_logs.len();
 
         // Calculate content first to determine if horizontal scrollbar is needed
-        let temp_inner_area =
-            self.logs_block
-                .get_content_rect(content_area, is_log_focused, self.mode_color);
+        let temp_inner_area = self.logs_block.get_content_rect(
+            content_area,
+            &self.theme,
+            is_log_focused,
+            self.mode_color,
+        );
         let viewport_width = temp_inner_area.width as usize;
 
         // Since we're using truncated mode, content will never exceed the viewport width
@@ -464,7 +643,7 @@ impl App {
                 && !self.suppress_mouse_up;
             let inner_area = self
                 .logs_block
-                .build(false, self.mode_color)
+                .build(&self.theme, false, self.mode_color)
                 .inner(main_content_area);
             let is_within_bounds =
                 inner_area.contains(ratatui::layout::Position::new(event.column, event.row));
@@ -489,9 +668,12 @@ impl App {
             self.set_hard_focused_block(logs_block_id);
         }
 
-        let inner_area =
-            self.logs_block
-                .get_content_rect(main_content_area, is_log_focused, self.mode_color);
+        let inner_area = self.logs_block.get_content_rect(
+            main_content_area,
+            &self.theme,
+            is_log_focused,
+            self.mode_color,
+        );
         let visible_height = inner_area.height as usize;
         let content_width = inner_area.width as usize;
 
@@ -519,26 +701,49 @@ impl App {
             let log_item = &self.raw_logs[raw_idx];
 
             let detail_text = self.parser.format_preview(log_item, self.detail_level);
-            let level = log_item.get_metadata("level").unwrap_or("").to_uppercase();
-            let level_style = match level.as_str() {
-                "ERROR" => theme::ERROR_STYLE,
-                "WARNING" | "WARN" => theme::WARN_STYLE,
-                "SYSTEM" => theme::INFO_STYLE,
-                _ => Style::default().fg(theme::TEXT_FG_COLOR),
-            };
+            let mut level_style = self.theme.severity_style(self.parser.severity(log_item));
+            let is_context = self.filter_engine.is_context_line(raw_idx);
+            if is_context {
+                level_style = level_style.patch(self.theme.context);
+            }
+            // with context lines, mark where the displayed ranges are discontinuous
+            let starts_new_range = self.filter_engine.shows_context()
+                && i > 0
+                && self
+                    .displaying_logs
+                    .get(i - 1)
+                    .is_some_and(|prev_idx| prev_idx + 1 != raw_idx);
 
             let is_cursor = selected_index == Some(i);
             let is_visual_selected =
                 visual_selection_range.is_some_and(|(start, end)| i >= start && i <= end);
             let is_selected = is_cursor || is_visual_selected;
-            let display_text = if is_cursor {
-                format!(" → {}", detail_text)
+            let gutter = if self.bookmarks.contains(&log_item.id) {
+                '●'
             } else {
-                format!("   {}", detail_text)
+                ' '
             };
+            let pointer = if is_cursor {
+                '→'
+            } else if starts_new_range {
+                '┄'
+            } else {
+                ' '
+            };
+            let marker = format!("{}{} ", gutter, pointer);
+            // merged sources are tagged with their name, in the source color
+            let source_tag = log_item
+                .get_metadata(SOURCE_METADATA_KEY)
+                .and_then(|name| Some((name, *self.source_colors.get(name)?)))
+                .map(|(name, color)| {
+                    let tag = format!("{:<width$} ", name, width = self.source_tag_width);
+                    (tag, color)
+                });
+            let tag_text = source_tag.as_ref().map_or("", |(tag, _)| tag.as_str());
+            let display_text = format!("{}{}{}", marker, tag_text, detail_text);
 
             let final_style = if is_selected {
-                level_style.patch(theme::SELECTED_STYLE)
+                level_style.patch(self.theme.selected)
             } else {
                 level_style
             };
@@ -555,16 +760,25 @@ impl App {
 
             let truncated_text = truncated_line.to_string();
 
-            // apply highlighting if filter is active
-            let final_line = if !filter_query.is_empty() {
-                let highlighted_line =
-                    create_highlighted_line(&truncated_text, &filter_query, final_style);
+            // apply highlighting if filter or search is active
+            let final_line = if !highlight_queries.is_empty() {
+                let highlighted_line = create_highlighted_line(
+                    &truncated_text,
+                    &highlight_queries,
+                    final_style,
+                    self.theme.highlight,
+                );
 
                 // add padding for selected items
                 if is_selected {
                     let padded_text = format!("{:<width$}", truncated_text, width = content_width);
                     // re-apply highlighting to padded text
-                    create_highlighted_line(&padded_text, &filter_query, final_style)
+                    create_highlighted_line(
+                        &padded_text,
+                        &highlight_queries,
+                        final_style,
+                        self.theme.highlight,
+                    )
                 } else {
                     highlighted_line
                 }
@@ -576,6 +790,14 @@ impl App {
                 };
                 Line::styled(padded_text, final_style)
             };
+            let final_line = match &source_tag {
+                Some((tag, color)) => patch_line_range(
+                    final_line,
+                    marker.len()..marker.len() + tag.len(),
+                    Style::new().fg(*color),
+                ),
+                None => final_line,
+            };
 
             content_lines.push(final_line);
         }
@@ -591,7 +813,9 @@ impl App {
         let scrollbar_content_length = total_lines.saturating_sub(visible_height);
         logs_block.update_scrollbar_state(scrollbar_content_length, Some(scroll_position));
 
-        let block = self.logs_block.build(is_log_focused, self.mode_color);
+        let block = self
+            .logs_block
+            .build(&self.theme, is_log_focused, self.mode_color);
 
         // Determine if horizontal scrollbar is needed
         let needs_horizontal_scrollbar = max_content_width > viewport_width;
@@ -603,11 +827,11 @@ impl App {
 
         Paragraph::new(content_lines)
             .block(block)
-            .fg(theme::TEXT_FG_COLOR)
+            .fg(self.theme.text)
             .scroll((0, h_scroll))
             .render(main_content_area, buf);
 
-        let scrollbar = AppBlock::create_scrollbar(is_log_focused, self.mode_color);
+        let scrollbar = AppBlock::create_scrollbar(&self.theme, is_log_focused, self.mode_color);
         let logs_block = &mut self.logs_block;
         StatefulWidget::render(
             scrollbar,
@@ -618,9 +842,9 @@ impl App {
 
         // Always render horizontal scrollbar area (track-only when not needed)
         let horizontal_scrollbar = if needs_horizontal_scrollbar {
-            AppBlock::create_horizontal_scrollbar(is_log_focused, self.mode_color)
+            AppBlock::create_horizontal_scrollbar(&self.theme, is_log_focused, self.mode_color)
         } else {
-            AppBlock::create_horizontal_track_only(is_log_focused, self.mode_color)
+            AppBlock::create_horizontal_track_only(&self.theme, is_log_focused, self.mode_color)
         };
         StatefulWidget::render(
             horizontal_scrollbar,
@@ -646,6 +870,7 @@ impl App {
                 self.details_block.set_scroll_position(0);
                 self.details_block.set_horizontal_scroll_position(0);
             }
+            self.details_targets.clear();
 
             return self.render_scrollable_block(
                 area,
@@ -669,6 +894,9 @@ impl App {
                 self.prev_selected_log_id = Some(item.id);
                 self.details_block.set_scroll_position(0);
                 self.details_block.set_horizontal_scroll_position(0);
+                // look for JSON payloads once per selected log, not on every frame
+                self.details_segments = split_json(&item.content);
+                self.details_folds.clear();
             }
         } else if self.prev_selected_log_id.is_some() {
             self.prev_selected_log_id = None;
@@ -685,11 +913,14 @@ impl App {
                 let raw_idx = indices[i];
                 let item = &self.raw_logs[raw_idx];
 
-                Some((
-                    item.time.clone(),
-                    item.metadata.clone(),
-                    item.content.clone(),
-                ))
+                // show the full date when the parser read it from the source
+                let time = item.timestamp.map_or_else(
+                    || item.time.clone(),
+                    |timestamp| timestamp.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+                );
+
+                let severity_style = self.theme.severity_style(self.parser.severity(item));
+                Some((time, item.metadata.clone(), severity_style))
             } else {
                 None
             }
@@ -698,39 +929,60 @@ impl App {
         let text_wrapping_enabled = self.text_wrapping_enabled;
 
         // generate content using the cloned data
-        let (content, max_content_width) = if let Some((time, metadata, item_content)) =
-            &selected_item
+        let (content, max_content_width) = if let Some((time, metadata, severity_style)) =
+            selected_item
         {
-            // start with time field
-            let mut content_lines = vec![Line::from(vec!["Time: ".bold(), time.clone().into()])];
-
             // define preferred display order for common metadata fields
             let preferred_order = ["level", "origin", "tag"];
+            let capitalize =
+                |key: &str| key.chars().next().unwrap().to_uppercase().to_string() + &key[1..];
 
-            // add metadata fields in preferred order if they exist
+            // time and metadata as a key/value table, preferred fields first
+            let mut rows = vec![("Time".to_string(), time, Style::new())];
             for key in &preferred_order {
                 if let Some(value) = metadata.get(*key) {
-                    let label = format!(
-                        "{}: ",
-                        key.chars().next().unwrap().to_uppercase().to_string() + &key[1..]
-                    );
-                    content_lines.push(Line::from(vec![label.bold(), value.clone().into()]));
+                    let style = if *key == "level" {
+                        severity_style
+                    } else {
+                        Style::new()
+                    };
+                    rows.push((capitalize(key), value.clone(), style));
                 }
             }
-
-            // add any other metadata fields not in the preferred order
-            for (key, value) in metadata.iter() {
-                if !preferred_order.contains(&key.as_str()) {
-                    let label = format!(
-                        "{}: ",
-                        key.chars().next().unwrap().to_uppercase().to_string() + &key[1..]
-                    );
-                    content_lines.push(Line::from(vec![label.bold(), value.clone().into()]));
-                }
+            let mut other_keys: Vec<&String> = metadata
+                .keys()
+                .filter(|key| !preferred_order.contains(&key.as_str()))
+                .collect();
+            other_keys.sort();
+            for key in other_keys {
+                rows.push((capitalize(key), metadata[key].clone(), Style::new()));
             }
 
+            let label_width = rows
+                .iter()
+                .map(|(label, _, _)| label.width())
+                .max()
+                .unwrap_or(0);
+            let mut content_lines: Vec<DetailsLine> = rows
+                .into_iter()
+                .map(|(label, value, style)| {
+                    let padding = " ".repeat(label_width - label.width() + 2);
+                    let value_start = label_width + 2;
+                    let target = ClickTarget {
+                        columns: value_start..value_start + value.width(),
+                        action: ClickAction::Copy(value.clone()),
+                    };
+                    let line = Line::from(vec![
+                        label.bold(),
+                        padding.into(),
+                        Span::styled(value, style),
+                    ]);
+                    (line, vec![target])
+                })
+                .collect();
+
             // add content field
-            content_lines.push(Line::from("Content:".bold()));
+            content_lines.push((Line::from("Content:".bold()), Vec::new()));
 
             // calculate temp_content_rect to determine wrapping width
             let [vertical_content_area, _] =
@@ -743,48 +995,67 @@ impl App {
                 .areas(vertical_content_area);
 
             let is_focused = self.get_display_focused_block() == self.details_block.id();
-            let temp_content_rect =
-                self.details_block
-                    .get_content_rect(content_area, is_focused, self.mode_color);
+            let temp_content_rect = self.details_block.get_content_rect(
+                content_area,
+                &self.theme,
+                is_focused,
+                self.mode_color,
+            );
 
             let wrapping_mode = if text_wrapping_enabled {
                 WrappingMode::Wrapped
             } else {
                 WrappingMode::Unwrapped
             };
-            content_lines.extend(content_into_lines(
-                item_content,
-                temp_content_rect.width,
-                wrapping_mode,
-            ));
 
-            // calculate max content width for horizontal scrolling
-            let max_content_width = if text_wrapping_enabled {
-                temp_content_rect.width as usize
-            } else {
-                // calculate header widths dynamically based on actual fields
-                let mut header_widths = vec!["Time: ".len()];
-                for key in &preferred_order {
-                    if metadata.contains_key(*key) {
-                        let label_len = key.len() + 2; // +2 for ": "
-                        header_widths.push(label_len);
+            // JSON payloads are pretty-printed and never wrapped, the text around them is
+            self.details_fold_paths.clear();
+            let has_json = self.details_segments.len() > 1
+                || matches!(self.details_segments.first(), Some(ContentSegment::Json(_)));
+            for (index, segment) in self.details_segments.iter().enumerate() {
+                match segment {
+                    ContentSegment::Text(text) => {
+                        let text = if has_json { text.trim() } else { text.as_str() };
+                        if text.is_empty() {
+                            continue;
+                        }
+                        content_lines.extend(
+                            content_into_lines(text, temp_content_rect.width, wrapping_mode)
+                                .into_iter()
+                                .map(|line| (line.patch_style(severity_style), Vec::new())),
+                        );
                     }
-                }
-                for key in metadata.keys() {
-                    if !preferred_order.contains(&key.as_str()) {
-                        let label_len = key.len() + 2; // +2 for ": "
-                        header_widths.push(label_len);
+                    ContentSegment::Json(value) => {
+                        let root = index.to_string();
+                        content_lines.extend(json_lines(
+                            value,
+                            &root,
+                            &self.details_folds,
+                            &self.theme,
+                        ));
+                        self.details_fold_paths
+                            .extend(nested_fold_paths(value, &root));
                     }
                 }
-                header_widths.push("Content:".len());
+            }
 
-                let header_width = header_widths.iter().max().copied().unwrap_or(0);
-                let item_content_width = calculate_content_width(item_content);
-                header_width.max(item_content_width)
+            // calculate max content width for horizontal scrolling
+            let widest_line = content_lines
+                .iter()
+                .map(|(line, _)| line.width())
+                .max()
+                .unwrap_or(0);
+            let max_content_width = if text_wrapping_enabled {
+                widest_line.max(temp_content_rect.width as usize)
+            } else {
+                widest_line
             };
 
-            (content_lines, max_content_width)
+            let (content, targets) = content_lines.into_iter().unzip();
+            self.details_targets = targets;
+            (content, max_content_width)
         } else {
+            self.details_targets.clear();
             (
                 vec![Line::from("Select a log item to see details...".italic())],
                 0,
@@ -812,13 +1083,13 @@ impl App {
                     .map(|log_entry| {
                         let log_upper = log_entry.to_uppercase();
                         let style = if log_upper.contains("ERROR") {
-                            theme::ERROR_STYLE
+                            self.theme.error
                         } else if log_upper.contains("WARNING") || log_upper.contains("WARN") {
-                            theme::WARN_STYLE
+                            self.theme.warn
                         } else if log_upper.contains("DEBUG") {
-                            theme::DEBUG_STYLE
+                            self.theme.debug
                         } else {
-                            theme::INFO_STYLE
+                            self.theme.info
                         };
                         Line::styled(log_entry.clone(), style)
                     })
@@ -867,6 +1138,35 @@ impl App {
             return Ok(());
         }
 
+        let row_in_view = mouse.row.saturating_sub(content_rect.y) as usize;
+        let line_index = vertical_scroll.saturating_add(row_in_view);
+
+        let Some(line) = content.get(line_index) else {
+            return Ok(());
+        };
+
+        let col_in_view = mouse.column.saturating_sub(content_rect.x) as usize;
+        let display_col = horizontal_scroll.saturating_add(col_in_view);
+
+        let target = self
+            .details_targets
+            .get(line_index)
+            .and_then(|targets| {
+                targets
+                    .iter()
+                    .find(|target| target.columns.contains(&display_col))
+            })
+            .map(|target| target.action.clone());
+
+        // a single click on a fold marker folds or unfolds its JSON container
+        if let Some(ClickAction::Fold(path)) = target {
+            if !self.details_folds.remove(&path) {
+                self.details_folds.insert(path);
+            }
+            self.last_click_time = None;
+            return Ok(());
+        }
+
         let now = Instant::now();
         let same_cell = self.last_click_pos == Some((mouse.column, mouse.row));
         let within_window = self
@@ -882,19 +1182,16 @@ impl App {
             return Ok(()); // require a double-click on the same cell
         }
 
-        let row_in_view = mouse.row.saturating_sub(content_rect.y) as usize;
-        let line_index = vertical_scroll.saturating_add(row_in_view);
-
-        let Some(line) = content.get(line_index) else {
-            return Ok(());
-        };
-
-        let col_in_view = mouse.column.saturating_sub(content_rect.x) as usize;
-        let display_col = horizontal_scroll.saturating_add(col_in_view);
-
-        let text = line.to_string();
-        let Some(word) = find_word_at_display_column(&text, display_col) else {
-            return Ok(());
+        // values in the metadata table and JSON payloads are copied whole
+        let word = match target {
+            Some(ClickAction::Copy(value)) => value,
+            _ => {
+                let text = line.to_string();
+                let Some(word) = find_word_at_display_column(&text, display_col) else {
+                    return Ok(());
+                };
+                word
+            }
         };
 
         let mut clipboard = Clipboard::new()?;
@@ -913,11 +1210,11 @@ impl App {
 
         self.set_display_event(
             format!("copied \"{}\" to clipboard", display_word),
-            Duration::from_millis(super::DISPLAY_EVENT_DURATION_MS),
+            self.display_event_duration,
             None,
         );
 
-        log::debug!("Copied word from details panel: {}", word);
+        log::debug!("Copied from details panel: {}", word);
 
         Ok(())
     }
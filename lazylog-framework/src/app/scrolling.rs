@@ -1,4 +1,4 @@
-use super::{App, HORIZONTAL_SCROLL_STEP};
+use super::App;
 use anyhow::Result;
 use ratatui::prelude::*;
 
@@ -21,9 +21,12 @@ impl App {
                 .margin(0)
                 .areas(main_content_area);
 
-            let inner_area =
-                self.logs_block
-                    .get_content_rect(content_area, is_focused, self.mode_color);
+            let inner_area = self.logs_block.get_content_rect(
+                content_area,
+                &self.theme,
+                is_focused,
+                self.mode_color,
+            );
             inner_area.height as usize
         } else {
             1 // fallback if area not yet rendered
@@ -79,6 +82,57 @@ impl App {
         Ok(())
     }
 
+    /// select the first (oldest) log and scroll to it
+    pub(super) fn jump_to_top(&mut self) {
+        if self.displaying_logs.len() > 0 {
+            self.displaying_logs.state.select(Some(0));
+            self.update_selected_uuid();
+        }
+
+        self.logs_block.set_scroll_position(0);
+        self.update_autoscroll_state();
+        self.update_logs_scrollbar_state();
+    }
+
+    /// select the last (newest) log and scroll to the bottom
+    pub(super) fn jump_to_bottom(&mut self) {
+        self.displaying_logs.select_last();
+        self.update_selected_uuid();
+
+        // scroll to bottom (stop when last item is fully displayed)
+        let total_items = self.displaying_logs.len();
+
+        // calculate viewport height to determine max scroll position
+        let viewport_height = if let Some(area) = self.last_logs_area {
+            let is_focused = self.is_log_block_focused().unwrap_or(false);
+            let [main_content_area, _] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(1)])
+                    .margin(0)
+                    .areas(area);
+
+            let [content_area, _] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)])
+                .margin(0)
+                .areas(main_content_area);
+
+            let inner_area = self.logs_block.get_content_rect(
+                content_area,
+                &self.theme,
+                is_focused,
+                self.mode_color,
+            );
+            inner_area.height as usize
+        } else {
+            1 // fallback if area not yet rendered
+        };
+
+        let max_scroll = total_items.saturating_sub(viewport_height);
+        self.logs_block.set_scroll_position(max_scroll);
+        // force autoscroll to be true so that we don't wait for the next render to update the scrollbar state
+        // waiting for the next render may cause new logs arrive beforehand, thus the view is not at the bottom
+        self.update_autoscroll_state();
+        self.update_logs_scrollbar_state();
+    }
+
     pub(super) fn handle_log_item_scrolling(
         &mut self,
         move_next: bool,
@@ -120,9 +174,12 @@ impl App {
                 .margin(0)
                 .areas(main_content_area);
 
-            let inner_area =
-                self.logs_block
-                    .get_content_rect(content_area, is_focused, self.mode_color);
+            let inner_area = self.logs_block.get_content_rect(
+                content_area,
+                &self.theme,
+                is_focused,
+                self.mode_color,
+            );
             inner_area.height as usize
         } else {
             1 // fallback if area not yet rendered
@@ -239,7 +296,7 @@ impl App {
         ])
         .margin(0)
         .areas(main_content_area);
-        let content_rect = block.get_content_rect(content_area, true, self.mode_color);
+        let content_rect = block.get_content_rect(content_area, &self.theme, true, self.mode_color);
         let viewport_width = content_rect.width as usize;
 
         if content_width <= viewport_width {
@@ -249,10 +306,10 @@ impl App {
         let max_scroll = content_width.saturating_sub(viewport_width);
         let new_position = if move_right {
             current_position
-                .saturating_add(HORIZONTAL_SCROLL_STEP)
+                .saturating_add(self.horizontal_scroll_step)
                 .min(max_scroll)
         } else {
-            current_position.saturating_sub(HORIZONTAL_SCROLL_STEP)
+            current_position.saturating_sub(self.horizontal_scroll_step)
         };
 
         block.set_horizontal_scroll_position(new_position);
@@ -293,9 +350,12 @@ impl App {
                     .areas(content_area);
 
             let is_focused = self.get_display_focused_block() == self.logs_block.id();
-            let inner_area =
-                self.logs_block
-                    .get_content_rect(main_content_area, is_focused, self.mode_color);
+            let inner_area = self.logs_block.get_content_rect(
+                main_content_area,
+                &self.theme,
+                is_focused,
+                self.mode_color,
+            );
             let viewport_height = inner_area.height as usize;
             let max_scroll = lines_count.saturating_sub(viewport_height);
 
@@ -362,9 +422,12 @@ impl App {
                     .areas(main_content_area);
 
             let is_focused = self.get_display_focused_block() == self.logs_block.id();
-            let content_rect =
-                self.logs_block
-                    .get_content_rect(content_area, is_focused, self.mode_color);
+            let content_rect = self.logs_block.get_content_rect(
+                content_area,
+                &self.theme,
+                is_focused,
+                self.mode_color,
+            );
             let viewport_width = content_rect.width as usize;
             let content_width = self.logs_block.get_content_width();
 
@@ -408,9 +471,12 @@ impl App {
                     .areas(main_content_area);
 
             let is_focused = self.get_display_focused_block() == self.details_block.id();
-            let content_rect =
-                self.details_block
-                    .get_content_rect(content_area, is_focused, self.mode_color);
+            let content_rect = self.details_block.get_content_rect(
+                content_area,
+                &self.theme,
+                is_focused,
+                self.mode_color,
+            );
             let viewport_width = content_rect.width as usize;
             let content_width = self.details_block.get_content_width();
 
@@ -454,9 +520,12 @@ impl App {
                     .areas(main_content_area);
 
             let is_focused = self.get_display_focused_block() == self.debug_block.id();
-            let content_rect =
-                self.debug_block
-                    .get_content_rect(content_area, is_focused, self.mode_color);
+            let content_rect = self.debug_block.get_content_rect(
+                content_area,
+                &self.theme,
+                is_focused,
+                self.mode_color,
+            );
             let viewport_width = content_rect.width as usize;
             let content_width = self.debug_block.get_content_width();
 
@@ -0,0 +1,406 @@
+use super::{App, AppDesc};
+use crate::keymap::Action;
+use crate::provider::LogProvider;
+use anyhow::{Result, anyhow, bail};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{
+    backend::CrosstermBackend,
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+/// the open-tab picker lists this many sessions, picked with keys 1-9
+const MAX_PICKER_SESSIONS: usize = 9;
+
+type OpenSession = Box<dyn Fn() -> Result<(Box<dyn LogProvider>, AppDesc)>>;
+
+/// A session that can be opened as a tab, see [`start_with_tabs`].
+///
+/// `open` builds a fresh provider and configuration each time the session is
+/// opened, every tab runs its own provider thread.
+pub struct TabDesc {
+    name: String,
+    open: OpenSession,
+}
+
+impl TabDesc {
+    pub fn new<P, F>(name: impl Into<String>, open: F) -> Self
+    where
+        P: LogProvider + 'static,
+        F: Fn() -> Result<(P, AppDesc)> + 'static,
+    {
+        Self {
+            name: name.into(),
+            open: Box::new(move || {
+                let (provider, desc) = open()?;
+                Ok((Box::new(provider) as Box<dyn LogProvider>, desc))
+            }),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Start the application with every session open in its own tab
+///
+/// The tab actions of the [`Keymap`](crate::Keymap) switch, open and close tabs.
+/// Background tabs keep ingesting and show how many logs arrived since they were last viewed.
+pub fn start_with_tabs(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    sessions: Vec<TabDesc>,
+) -> Result<()> {
+    color_eyre::install().or(Err(anyhow!("Error installing color_eyre")))?;
+
+    if sessions.is_empty() {
+        bail!("No sessions to open");
+    }
+
+    let mut host = TabHost {
+        sessions,
+        tabs: Vec::new(),
+        active: 0,
+        picker_open: false,
+    };
+    for index in 0..host.sessions.len() {
+        host.open_tab(index)?;
+    }
+    host.active = 0;
+    host.run(terminal)
+}
+
+struct Tab {
+    name: String,
+    app: App,
+    poll_interval: Duration,
+    event_poll_interval: Duration,
+    last_update: Instant,
+    unread: usize, // Logs received while the tab was in the background
+}
+
+struct TabHost {
+    sessions: Vec<TabDesc>,
+    tabs: Vec<Tab>,
+    active: usize,
+    picker_open: bool, // Whether the open-tab picker is shown
+}
+
+// ============================================================================
+// Lifecycle
+// ============================================================================
+impl TabHost {
+    fn run(mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| -> Result<()> {
+            while !self.tabs[self.active].app.is_exiting {
+                self.poll_event()?;
+                self.update_tabs()?;
+
+                self.tabs[self.active].app.check_and_clear_expired_event();
+                terminal.draw(|frame| self.render(frame.area(), frame.buffer_mut()))?;
+            }
+            Ok(())
+        }));
+
+        // cleanup every provider thread before returning
+        for tab in &mut self.tabs {
+            tab.app.cleanup();
+        }
+
+        match result {
+            Ok(r) => r,
+            Err(_) => {
+                eprintln!("Application panicked, terminal restored");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    fn poll_event(&mut self) -> Result<()> {
+        let poll_interval = self
+            .tabs
+            .iter()
+            .map(|tab| tab.event_poll_interval)
+            .min()
+            .unwrap_or_default();
+
+        if event::poll(poll_interval)? {
+            match event::read()? {
+                Event::Key(key) => self.handle_key(key)?,
+                event => self.tabs[self.active].app.handle_event(event)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// every tab keeps ingesting, background tabs count what they receive
+    fn update_tabs(&mut self) -> Result<()> {
+        for (index, tab) in self.tabs.iter_mut().enumerate() {
+            if tab.last_update.elapsed() < tab.poll_interval {
+                continue;
+            }
+            let received = tab.app.update_logs()?;
+            if index != self.active {
+                tab.unread += received;
+            }
+            tab.last_update = Instant::now();
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tab management
+// ============================================================================
+impl TabHost {
+    fn open_tab(&mut self, session: usize) -> Result<()> {
+        let (provider, desc) = (self.sessions[session].open)()?;
+        let mut app = App::new(provider, desc.clone());
+        app.tabbed = true;
+        // mouse capture is a terminal setting, new tabs inherit it once the first is open
+        let mouse_capture = self
+            .tabs
+            .get(self.active)
+            .map_or(desc.mouse_capture, |tab| tab.app.mouse_capture_enabled);
+        app.set_mouse_capture(mouse_capture)?;
+
+        self.tabs.push(Tab {
+            name: self.sessions[session].name.clone(),
+            app,
+            poll_interval: desc.poll_interval,
+            event_poll_interval: desc.event_poll_interval,
+            last_update: Instant::now(),
+            unread: 0,
+        });
+        self.active = self.tabs.len() - 1;
+        Ok(())
+    }
+
+    /// open a session in a new tab, reporting a failure in the current tab
+    fn open_session(&mut self, session: usize) {
+        let name = self.sessions[session].name.clone();
+        if let Err(e) = self.open_tab(session) {
+            self.show_event(format!("failed to open {}: {}", name, e));
+        }
+    }
+
+    fn close_active_tab(&mut self) {
+        if self.tabs.len() == 1 {
+            let hint = self.tabs[self.active].app.keymap.hint(Action::Quit, "quit");
+            self.show_event(format!("cannot close the last tab{}", hint));
+            return;
+        }
+
+        let mut tab = self.tabs.remove(self.active);
+        tab.app.cleanup();
+        self.active = self.active.min(self.tabs.len() - 1);
+        self.select_tab_with_mouse_capture(self.active, tab.app.mouse_capture_enabled);
+        self.show_event(format!("closed {}", tab.name));
+    }
+
+    fn select_tab(&mut self, index: usize) {
+        let mouse_capture = self.tabs[self.active].app.mouse_capture_enabled;
+        self.select_tab_with_mouse_capture(index, mouse_capture);
+    }
+
+    /// the selected tab takes over the terminal's mouse capture state
+    fn select_tab_with_mouse_capture(&mut self, index: usize, mouse_capture: bool) {
+        self.active = index;
+        self.tabs[index].unread = 0;
+        self.tabs[index].app.mouse_capture_enabled = mouse_capture;
+    }
+
+    fn cycle_tab(&mut self, forward: bool) {
+        let len = self.tabs.len();
+        let index = if forward {
+            (self.active + 1) % len
+        } else {
+            (self.active + len - 1) % len
+        };
+        self.select_tab(index);
+    }
+
+    fn show_event(&mut self, text: String) {
+        let app = &mut self.tabs[self.active].app;
+        app.set_display_event(text, app.display_event_duration, None);
+    }
+}
+
+// ============================================================================
+// Event handling
+// ============================================================================
+impl TabHost {
+    fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        // the picker captures all input until a session is picked or it is cancelled
+        if self.picker_open {
+            if key.kind == KeyEventKind::Press {
+                self.picker_open = false;
+                let picked = match key.code {
+                    KeyCode::Char(c) => c.to_digit(10),
+                    _ => None,
+                };
+                if let Some(session) = picked
+                    .and_then(|digit| (digit as usize).checked_sub(1))
+                    .filter(|&session| session < self.sessions.len())
+                {
+                    self.open_session(session);
+                }
+            }
+            return Ok(());
+        }
+
+        // tab actions are bound in the app's keymap, it hands them back here
+        let app = &mut self.tabs[self.active].app;
+        app.handle_key(key)?;
+        match app.tab_action.take() {
+            Some(Action::NextTab) => self.cycle_tab(true),
+            Some(Action::PrevTab) => self.cycle_tab(false),
+            Some(Action::OpenTab) => {
+                if self.sessions.len() == 1 {
+                    self.open_session(0);
+                } else {
+                    self.picker_open = true;
+                }
+            }
+            Some(Action::CloseTab) => self.close_active_tab(),
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Rendering
+// ============================================================================
+impl TabHost {
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let [strip_area, app_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+
+        self.render_tab_strip(strip_area, buf);
+        self.tabs[self.active].app.render(app_area, buf);
+
+        if self.picker_open {
+            self.render_picker(area, buf);
+        }
+    }
+
+    fn render_tab_strip(&self, area: Rect, buf: &mut Buffer) {
+        let mut spans = Vec::new();
+        for (index, tab) in self.tabs.iter().enumerate() {
+            let color = tab.app.mode_color;
+            let mut label = format!(" {} {} ", index + 1, tab.name);
+            if tab.unread > 0 {
+                label += &format!("+{} ", tab.unread);
+            }
+
+            let style = if index == self.active {
+                tab.app.theme.active_tab.bg(color)
+            } else {
+                Style::new().fg(color)
+            };
+            spans.push(Span::styled(label, style));
+            spans.push(Span::raw(" "));
+        }
+
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+
+    fn render_picker(&self, area: Rect, buf: &mut Buffer) {
+        let lines: Vec<Line> = self
+            .sessions
+            .iter()
+            .take(MAX_PICKER_SESSIONS)
+            .enumerate()
+            .map(|(index, session)| Line::from(format!(" {}  {}", index + 1, session.name)))
+            .collect();
+
+        let width = self
+            .sessions
+            .iter()
+            .map(|session| session.name.chars().count() as u16 + 6)
+            .max()
+            .unwrap_or(0)
+            .max(24);
+        let height = lines.len() as u16 + 2;
+
+        let popup_area = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .split(area)[1];
+        let popup_area = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .split(popup_area)[1];
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title("Open tab (Esc cancel)")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.tabs[self.active].app.mode_color));
+
+        Paragraph::new(lines).block(block).render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{
+        PlainTextParser,
+        test_support::{OnceProvider, wait_for},
+    };
+    use std::sync::Arc;
+
+    fn session(name: &str, logs: usize) -> TabDesc {
+        TabDesc::new(name, move || {
+            let mut desc = AppDesc::new(Arc::new(PlainTextParser));
+            desc.poll_interval = Duration::from_millis(1);
+            Ok((OnceProvider::numbered(logs), desc))
+        })
+    }
+
+    #[test]
+    fn test_background_tabs_count_unread_logs() {
+        let mut host = TabHost {
+            sessions: vec![session("a", 2), session("b", 3)],
+            tabs: Vec::new(),
+            active: 0,
+            picker_open: false,
+        };
+        host.open_tab(0).unwrap();
+        host.open_tab(1).unwrap();
+        host.select_tab(0);
+
+        wait_for(|| {
+            host.update_tabs().unwrap();
+            host.tabs
+                .iter()
+                .map(|tab| tab.app.raw_logs.len())
+                .sum::<usize>()
+                == 5
+        });
+
+        assert_eq!(host.tabs[0].unread, 0);
+        assert_eq!(host.tabs[1].unread, 3);
+        host.cycle_tab(true);
+        assert_eq!(host.active, 1);
+        assert_eq!(host.tabs[1].unread, 0);
+
+        host.close_active_tab();
+        host.close_active_tab();
+        assert_eq!(host.tabs.len(), 1);
+        assert_eq!(host.tabs[0].name, "a");
+        host.tabs[0].app.cleanup();
+    }
+}
@@ -9,6 +9,8 @@ use ratatui::{
 };
 use uuid::Uuid;
 
+use crate::theme::Theme;
+
 fn brighten_color(color: Color) -> Color {
     match color {
         Color::Rgb(r, g, b) => {
@@ -25,9 +27,11 @@ fn brighten_color(color: Color) -> Color {
     }
 }
 
-pub fn get_border_color(focused: bool, mode_color: Color) -> Color {
+pub fn get_border_color(theme: &Theme, focused: bool, mode_color: Color) -> Color {
     if focused {
-        brighten_color(mode_color)
+        theme
+            .focused_border
+            .unwrap_or_else(|| brighten_color(mode_color))
     } else {
         mode_color
     }
@@ -80,17 +84,12 @@ impl AppBlock {
         self.id
     }
 
-    pub fn build(&self, focused: bool, mode_color: Color) -> Block<'_> {
+    pub fn build(&self, theme: &Theme, focused: bool, mode_color: Color) -> Block<'_> {
         let mut block = Block::default()
             .borders(Borders::TOP | Borders::LEFT)
             .border_type(BorderType::Rounded);
 
-        let border_color = if focused {
-            // brighten mode color for focused state (blend with white)
-            brighten_color(mode_color)
-        } else {
-            mode_color
-        };
+        let border_color = get_border_color(theme, focused, mode_color);
         block = block.border_style(Style::new().fg(border_color));
 
         if let Some(title) = &self.title {
@@ -187,9 +186,13 @@ impl AppBlock {
     }
 
     /// Creates a uniform scrollbar widget with consistent styling
-    pub fn create_scrollbar(focused: bool, mode_color: Color) -> Scrollbar<'static> {
-        let handle_color = if focused { Color::White } else { Color::Gray };
-        let track_color = get_border_color(focused, mode_color);
+    pub fn create_scrollbar(theme: &Theme, focused: bool, mode_color: Color) -> Scrollbar<'static> {
+        let handle_color = if focused {
+            theme.focused_scrollbar_thumb
+        } else {
+            theme.scrollbar_thumb
+        };
+        let track_color = get_border_color(theme, focused, mode_color);
 
         Scrollbar::new(ScrollbarOrientation::VerticalRight)
             .symbols(scrollbar::VERTICAL)
@@ -202,9 +205,17 @@ impl AppBlock {
     }
 
     /// Creates a horizontal scrollbar widget with consistent styling
-    pub fn create_horizontal_scrollbar(focused: bool, mode_color: Color) -> Scrollbar<'static> {
-        let handle_color = if focused { Color::White } else { Color::Gray };
-        let track_color = get_border_color(focused, mode_color);
+    pub fn create_horizontal_scrollbar(
+        theme: &Theme,
+        focused: bool,
+        mode_color: Color,
+    ) -> Scrollbar<'static> {
+        let handle_color = if focused {
+            theme.focused_scrollbar_thumb
+        } else {
+            theme.scrollbar_thumb
+        };
+        let track_color = get_border_color(theme, focused, mode_color);
 
         Scrollbar::new(ScrollbarOrientation::HorizontalBottom)
             .symbols(scrollbar::HORIZONTAL)
@@ -217,8 +228,12 @@ impl AppBlock {
     }
 
     /// Creates a horizontal scrollbar that only shows track (no thumb)
-    pub fn create_horizontal_track_only(focused: bool, mode_color: Color) -> Scrollbar<'static> {
-        let track_color = get_border_color(focused, mode_color);
+    pub fn create_horizontal_track_only(
+        theme: &Theme,
+        focused: bool,
+        mode_color: Color,
+    ) -> Scrollbar<'static> {
+        let track_color = get_border_color(theme, focused, mode_color);
 
         Scrollbar::new(ScrollbarOrientation::HorizontalBottom)
             .symbols(scrollbar::HORIZONTAL)
@@ -231,8 +246,14 @@ impl AppBlock {
     }
 
     /// Returns the content rectangle accounting for block borders
-    pub fn get_content_rect(&self, area: Rect, focused: bool, mode_color: Color) -> Rect {
-        self.build(focused, mode_color).inner(area)
+    pub fn get_content_rect(
+        &self,
+        area: Rect,
+        theme: &Theme,
+        focused: bool,
+        mode_color: Color,
+    ) -> Rect {
+        self.build(theme, focused, mode_color).inner(area)
     }
 }
 
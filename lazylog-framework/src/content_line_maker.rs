@@ -1,5 +1,6 @@
 use ratatui::text::Line;
 
+#[derive(Clone, Copy)]
 pub enum WrappingMode {
     Wrapped,
     Unwrapped,
@@ -46,15 +47,6 @@ pub fn content_into_lines(
     }
 }
 
-pub fn calculate_content_width(content: &str) -> usize {
-    let sanitized = sanitize_control_chars(content);
-    sanitized
-        .lines()
-        .map(|line| line.chars().count())
-        .max()
-        .unwrap_or(0)
-}
-
 fn content_to_unwrapped_lines(content: String) -> Vec<Line<'static>> {
     content.lines().map(|s| Line::from(s.to_string())).collect()
 }
@@ -1,6 +1,790 @@
-use crate::provider::{LogDetailLevel, LogItem, LogParser};
+use crate::provider::{LogDetailLevel, LogItem, LogParser, Severity};
+use anyhow::{Result, anyhow, bail};
 use rayon::prelude::*;
-use std::sync::Arc;
+use regex::{Regex, RegexBuilder};
+use std::{
+    collections::{BTreeSet, VecDeque},
+    sync::Arc,
+};
+
+/// how a filter query is matched against searchable text
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FilterMode {
+    /// case-insensitive substring match
+    #[default]
+    Literal,
+    /// case-sensitive substring match
+    CaseSensitive,
+    /// regular expression match
+    Regex,
+}
+
+impl FilterMode {
+    /// cycle to the next mode (literal -> case-sensitive -> regex -> literal)
+    pub fn next(self) -> Self {
+        match self {
+            FilterMode::Literal => FilterMode::CaseSensitive,
+            FilterMode::CaseSensitive => FilterMode::Regex,
+            FilterMode::Regex => FilterMode::Literal,
+        }
+    }
+
+    /// short label for the status bar
+    pub fn label(self) -> &'static str {
+        match self {
+            FilterMode::Literal => "literal",
+            FilterMode::CaseSensitive => "case",
+            FilterMode::Regex => "regex",
+        }
+    }
+}
+
+/// a filter query compiled once for a given mode
+#[derive(Clone, Debug)]
+pub struct Matcher {
+    regex: Regex,
+}
+
+impl Matcher {
+    /// compile `query` according to `mode`
+    ///
+    /// literal modes escape the query, so only regex mode can fail
+    pub fn new(query: &str, mode: FilterMode) -> Result<Self> {
+        let regex = match mode {
+            FilterMode::Literal => RegexBuilder::new(&regex::escape(query))
+                .case_insensitive(true)
+                .build(),
+            FilterMode::CaseSensitive => Regex::new(&regex::escape(query)),
+            FilterMode::Regex => Regex::new(query),
+        }
+        .map_err(|e| anyhow!("invalid regex: {}", short_regex_error(&e)))?;
+
+        Ok(Self { regex })
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+
+    /// byte ranges of all non-empty matches in `text`
+    pub fn find_ranges(&self, text: &str) -> Vec<(usize, usize)> {
+        self.regex
+            .find_iter(text)
+            .filter(|m| !m.is_empty())
+            .map(|m| (m.start(), m.end()))
+            .collect()
+    }
+}
+
+/// regex syntax errors span several lines (pattern, caret, message),
+/// keep only the final message so it fits in the footer
+fn short_regex_error(err: &regex::Error) -> String {
+    let message = err.to_string();
+    message
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .map(|line| line.trim().trim_start_matches("error: ").to_string())
+        .unwrap_or(message)
+}
+
+/// a parsed filter query
+///
+/// in literal and case-sensitive modes the query supports:
+/// - whitespace separated terms, all of which must match (`foo bar`)
+/// - `AND`, `OR` and `NOT` keywords (uppercase) and parentheses for grouping
+/// - quoted phrases (`"connection reset"`)
+/// - negation with a leading `-` (`-timeout`, `-"connection reset"`)
+/// - `key:value` qualifiers matched against `LogItem::metadata` (`level:error`, `tag:Effect`)
+///   plus the special keys `content` and `time`; items without the key fall back to
+///   matching the whole `key:value` token as text
+/// - `level:` values naming a known level are compared by [`Severity`], so `level:error`
+///   matches `E`, `Error` and `ERROR` items alike
+///
+/// in regex mode the whole query is a single pattern
+#[derive(Clone, Debug)]
+pub struct FilterQuery {
+    mode: FilterMode,
+    root: Option<Expr>,
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Atom(Atom),
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+
+#[derive(Clone, Debug)]
+struct Atom {
+    /// metadata key for `key:value` qualifiers, `None` for plain text terms
+    key: Option<String>,
+    /// the term as typed (the value part for qualifiers)
+    value: String,
+    matcher: Matcher,
+    /// matcher for the whole `key:value` token, used when the item lacks the key
+    fallback: Option<Matcher>,
+    /// severity of a `level:` qualifier naming a known level (`level:e`, `level:fault`)
+    severity: Option<Severity>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Term {
+        key: Option<String>,
+        value: String,
+        negated: bool,
+    },
+}
+
+/// per-item state while evaluating a query, the searchable text is built lazily
+struct EvalContext<'a> {
+    parser: &'a dyn LogParser,
+    item: &'a LogItem,
+    detail_level: LogDetailLevel,
+    searchable_text: Option<String>,
+}
+
+impl EvalContext<'_> {
+    fn searchable_text(&mut self) -> &str {
+        let (parser, item, detail_level) = (self.parser, self.item, self.detail_level);
+        self.searchable_text
+            .get_or_insert_with(|| parser.get_searchable_text(item, detail_level))
+    }
+}
+
+impl FilterQuery {
+    /// parse `input` according to `mode`
+    ///
+    /// returns an error for invalid regex patterns and unbalanced closing parentheses
+    pub fn parse(input: &str, mode: FilterMode) -> Result<Self> {
+        if mode == FilterMode::Regex {
+            let root = (!input.is_empty()).then(|| Atom::new(None, input.to_string(), mode));
+            return Ok(Self {
+                mode,
+                root: root.transpose()?.map(Expr::Atom),
+            });
+        }
+
+        let tokens = tokenize(input);
+        let mut parser = QueryParser {
+            tokens: &tokens,
+            pos: 0,
+            mode,
+        };
+        let root = parser.parse_or()?;
+        if parser.pos < tokens.len() {
+            bail!("unexpected ')'");
+        }
+
+        Ok(Self { mode, root })
+    }
+
+    /// check whether `item` matches this query
+    pub fn matches(
+        &self,
+        parser: &dyn LogParser,
+        item: &LogItem,
+        detail_level: LogDetailLevel,
+    ) -> bool {
+        let Some(root) = &self.root else {
+            return true;
+        };
+
+        let mut ctx = EvalContext {
+            parser,
+            item,
+            detail_level,
+            searchable_text: None,
+        };
+        root.eval(&mut ctx)
+    }
+
+    /// byte ranges in `text` matched by positive text terms, sorted and merged
+    pub fn highlight_ranges(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut ranges = Vec::new();
+        if let Some(root) = &self.root {
+            root.collect_highlights(text, false, &mut ranges);
+        }
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(usize, usize)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        merged
+    }
+
+    /// whether every item matching `self` is guaranteed to match `previous`
+    ///
+    /// only holds for conjunctions of positive terms where each previous term is
+    /// extended by a term of the same key, e.g. `err tag:eff` -> `error tag:effect foo`
+    pub fn narrows(&self, previous: &FilterQuery) -> bool {
+        if self.mode != previous.mode || self.mode == FilterMode::Regex {
+            return false;
+        }
+
+        let (Some(current), Some(previous)) =
+            (self.positive_conjunction(), previous.positive_conjunction())
+        else {
+            return false;
+        };
+
+        previous.iter().all(|prev| {
+            current.iter().any(|atom| {
+                keys_equal(&atom.key, &prev.key)
+                    && match (atom.severity, prev.severity) {
+                        (None, None) => self.mode.contains(&atom.value, &prev.value),
+                        // `level:e` -> `level:er` goes from a severity to a substring match
+                        (current, previous) => current.is_some() && current == previous,
+                    }
+            })
+        })
+    }
+
+    /// atoms of a query that is a plain conjunction of positive terms
+    fn positive_conjunction(&self) -> Option<Vec<&Atom>> {
+        match &self.root {
+            None => Some(Vec::new()),
+            Some(Expr::Atom(atom)) => Some(vec![atom]),
+            Some(Expr::And(exprs)) => exprs
+                .iter()
+                .map(|expr| match expr {
+                    Expr::Atom(atom) => Some(atom),
+                    _ => None,
+                })
+                .collect(),
+            Some(_) => None,
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, ctx: &mut EvalContext) -> bool {
+        match self {
+            Expr::Atom(atom) => atom.matches(ctx),
+            Expr::Not(expr) => !expr.eval(ctx),
+            Expr::And(exprs) => exprs.iter().all(|expr| expr.eval(ctx)),
+            Expr::Or(exprs) => exprs.iter().any(|expr| expr.eval(ctx)),
+        }
+    }
+
+    fn collect_highlights(&self, text: &str, negated: bool, ranges: &mut Vec<(usize, usize)>) {
+        match self {
+            Expr::Atom(atom) => {
+                if !negated && atom.key.is_none() {
+                    ranges.extend(atom.matcher.find_ranges(text));
+                }
+            }
+            Expr::Not(expr) => expr.collect_highlights(text, !negated, ranges),
+            Expr::And(exprs) | Expr::Or(exprs) => {
+                for expr in exprs {
+                    expr.collect_highlights(text, negated, ranges);
+                }
+            }
+        }
+    }
+}
+
+impl Atom {
+    fn new(key: Option<String>, value: String, mode: FilterMode) -> Result<Self> {
+        let matcher = Matcher::new(&value, mode)?;
+        let fallback = match &key {
+            Some(key) => Some(Matcher::new(&format!("{}:{}", key, value), mode)?),
+            None => None,
+        };
+        let severity = key
+            .as_deref()
+            .filter(|key| key.eq_ignore_ascii_case("level"))
+            .and_then(|_| Severity::from_level(&value));
+
+        Ok(Self {
+            key,
+            value,
+            matcher,
+            fallback,
+            severity,
+        })
+    }
+
+    fn matches(&self, ctx: &mut EvalContext) -> bool {
+        let Some(key) = &self.key else {
+            return self.matcher.is_match(ctx.searchable_text());
+        };
+
+        // compare levels by severity so `level:error` also matches `E` and `Err`
+        if let (Some(wanted), Some(severity)) = (self.severity, ctx.parser.severity(ctx.item)) {
+            return wanted == severity;
+        }
+
+        match field_value(ctx.item, key) {
+            Some(value) => self.matcher.is_match(value),
+            None => self
+                .fallback
+                .as_ref()
+                .is_some_and(|fallback| fallback.is_match(ctx.searchable_text())),
+        }
+    }
+}
+
+/// value of a qualifier key on an item, metadata keys are matched case-insensitively
+fn field_value<'a>(item: &'a LogItem, key: &str) -> Option<&'a str> {
+    if key.eq_ignore_ascii_case("content") {
+        return Some(&item.content);
+    }
+    if key.eq_ignore_ascii_case("time") {
+        return Some(&item.time);
+    }
+
+    item.metadata
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        .map(|(_, v)| v.as_str())
+}
+
+fn keys_equal(a: &Option<String>, b: &Option<String>) -> bool {
+    match (a, b) {
+        (None, None) => true,
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        _ => false,
+    }
+}
+
+impl FilterMode {
+    /// substring check with the same case handling as the mode's matcher
+    fn contains(self, haystack: &str, needle: &str) -> bool {
+        match self {
+            FilterMode::Literal => haystack.to_lowercase().contains(&needle.to_lowercase()),
+            FilterMode::CaseSensitive | FilterMode::Regex => haystack.contains(needle),
+        }
+    }
+}
+
+struct QueryParser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    mode: FilterMode,
+}
+
+impl<'a> QueryParser<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    // or_expr := and_expr (OR and_expr)*
+    fn parse_or(&mut self) -> Result<Option<Expr>> {
+        let mut branches = Vec::new();
+        branches.extend(self.parse_and()?);
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            branches.extend(self.parse_and()?);
+        }
+        Ok(collapse(branches, Expr::Or))
+    }
+
+    // and_expr := unary (AND? unary)*
+    fn parse_and(&mut self) -> Result<Option<Expr>> {
+        let mut terms = Vec::new();
+        loop {
+            match self.peek() {
+                None | Some(Token::Or) | Some(Token::RParen) => break,
+                Some(Token::And) => self.pos += 1,
+                _ => terms.extend(self.parse_unary()?),
+            }
+        }
+        Ok(collapse(terms, Expr::And))
+    }
+
+    // unary := NOT unary | '(' or_expr ')' | term
+    //
+    // dangling operators (e.g. while typing `foo OR`) are ignored
+    fn parse_unary(&mut self) -> Result<Option<Expr>> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.pos += 1;
+                Ok(self.parse_unary()?.map(|expr| Expr::Not(Box::new(expr))))
+            }
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let inner = self.parse_or()?;
+                // a missing ')' is implied at the end of the query
+                if self.peek() == Some(&Token::RParen) {
+                    self.pos += 1;
+                }
+                Ok(inner)
+            }
+            Some(Token::Term {
+                key,
+                value,
+                negated,
+            }) => {
+                self.pos += 1;
+                let atom = Expr::Atom(Atom::new(key.clone(), value.clone(), self.mode)?);
+                Ok(Some(if *negated {
+                    Expr::Not(Box::new(atom))
+                } else {
+                    atom
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+fn collapse(mut exprs: Vec<Expr>, combine: fn(Vec<Expr>) -> Expr) -> Option<Expr> {
+    match exprs.len() {
+        0 => None,
+        1 => exprs.pop(),
+        _ => Some(combine(exprs)),
+    }
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '(' {
+            tokens.push(Token::LParen);
+            depth += 1;
+            i += 1;
+            continue;
+        }
+        if c == ')' {
+            tokens.push(Token::RParen);
+            depth = depth.saturating_sub(1);
+            i += 1;
+            continue;
+        }
+
+        // `-(...)` negates the whole group
+        if c == '-' && chars.get(i + 1) == Some(&'(') {
+            tokens.push(Token::Not);
+            i += 1;
+            continue;
+        }
+
+        let negated = c == '-' && chars.get(i + 1).is_some_and(|next| !next.is_whitespace());
+        if negated {
+            i += 1;
+        }
+
+        // quoted phrase, never a keyword or qualifier
+        if chars[i] == '"' {
+            let (value, next) = read_quoted(&chars, i);
+            i = next;
+            if !value.is_empty() {
+                tokens.push(Token::Term {
+                    key: None,
+                    value,
+                    negated,
+                });
+            }
+            continue;
+        }
+
+        // qualifier with a quoted value: key:"some value"
+        let key_len = qualifier_key_len(&chars[i..]);
+        if key_len > 0 && chars.get(i + key_len + 1) == Some(&'"') {
+            let key: String = chars[i..i + key_len].iter().collect();
+            let (value, next) = read_quoted(&chars, i + key_len + 1);
+            i = next;
+            if !value.is_empty() {
+                tokens.push(Token::Term {
+                    key: Some(key),
+                    value,
+                    negated,
+                });
+            }
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len() && !chars[i].is_whitespace() {
+            i += 1;
+        }
+        let mut word: String = chars[start..i].iter().collect();
+
+        // trailing ')' close open groups, otherwise they are part of the word (e.g. `init()`)
+        let mut closing = 0;
+        while depth > 0 && word.len() > 1 && word.ends_with(')') {
+            word.pop();
+            depth -= 1;
+            closing += 1;
+        }
+
+        let token = match word.as_str() {
+            "AND" if !negated => Token::And,
+            "OR" if !negated => Token::Or,
+            "NOT" if !negated => Token::Not,
+            _ => {
+                let (key, value) = split_qualifier(&word);
+                Token::Term {
+                    key,
+                    value,
+                    negated,
+                }
+            }
+        };
+        tokens.push(token);
+        tokens.extend((0..closing).map(|_| Token::RParen));
+    }
+
+    tokens
+}
+
+/// read a `"..."` phrase starting at the opening quote, an unterminated phrase runs to the end
+fn read_quoted(chars: &[char], open: usize) -> (String, usize) {
+    let start = open + 1;
+    let end = chars[start..]
+        .iter()
+        .position(|&c| c == '"')
+        .map_or(chars.len(), |offset| start + offset);
+    let value = chars[start..end].iter().collect();
+    (value, (end + 1).min(chars.len()))
+}
+
+/// length of a `key` followed by ':' at the start of `chars`, 0 if there is none
+fn qualifier_key_len(chars: &[char]) -> usize {
+    let Some(first) = chars.first() else {
+        return 0;
+    };
+    if !(first.is_ascii_alphabetic() || *first == '_') {
+        return 0;
+    }
+
+    let len = chars
+        .iter()
+        .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+        .count();
+    if chars.get(len) == Some(&':') { len } else { 0 }
+}
+
+/// split `key:value`, words without a valid key or with an empty value are plain text
+fn split_qualifier(word: &str) -> (Option<String>, String) {
+    let chars: Vec<char> = word.chars().collect();
+    let key_len = qualifier_key_len(&chars);
+    if key_len > 0 && key_len + 1 < chars.len() {
+        let key = chars[..key_len].iter().collect();
+        let value = chars[key_len + 1..].iter().collect();
+        (Some(key), value)
+    } else {
+        (None, word.to_string())
+    }
+}
+
+/// Severity-based visibility, combined with the text query by [`FilterEngine`].
+///
+/// Uses [`LogParser::severity`], so it behaves the same for every parser.
+/// Items without a known severity are always shown.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LevelFilter {
+    /// hide everything less severe than this
+    min_level: Option<Severity>,
+    /// individually hidden severities
+    hidden: BTreeSet<Severity>,
+}
+
+impl LevelFilter {
+    pub fn min_level(&self) -> Option<Severity> {
+        self.min_level
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.min_level.is_some() || !self.hidden.is_empty()
+    }
+
+    pub fn allows(&self, severity: Option<Severity>) -> bool {
+        let Some(severity) = severity else {
+            return true;
+        };
+        self.min_level.is_none_or(|min| severity >= min) && !self.hidden.contains(&severity)
+    }
+
+    /// step the minimum level up (`none -> D -> I -> ... -> F -> none`)
+    pub fn cycle_min_level(&mut self) {
+        self.min_level = match self.min_level {
+            None => Some(Severity::Debug),
+            Some(Severity::Fatal) => None,
+            Some(min) => Severity::ALL.into_iter().find(|&severity| severity > min),
+        };
+    }
+
+    /// hide or show a single severity, returns true if it is now hidden
+    pub fn toggle(&mut self, severity: Severity) -> bool {
+        if !self.hidden.remove(&severity) {
+            self.hidden.insert(severity);
+        }
+        self.hidden.contains(&severity)
+    }
+
+    /// whether everything this filter shows was also shown by `previous`
+    pub fn narrows(&self, previous: &LevelFilter) -> bool {
+        let min_narrows = match (self.min_level, previous.min_level) {
+            (_, None) => true,
+            (Some(min), Some(previous_min)) => min >= previous_min,
+            (None, Some(_)) => false,
+        };
+        min_narrows && self.hidden.is_superset(&previous.hidden)
+    }
+
+    /// compact description for the status bar, e.g. `level>=I -W`, `None` when inactive
+    pub fn indicator(&self) -> Option<String> {
+        if !self.is_active() {
+            return None;
+        }
+
+        let mut parts = vec!["level".to_string()];
+        if let Some(min) = self.min_level {
+            parts[0].push_str(&format!(">={}", min.letter()));
+        }
+        // levels already below the minimum are hidden anyway
+        parts.extend(
+            self.hidden
+                .iter()
+                .filter(|&&severity| self.min_level.is_none_or(|min| severity >= min))
+                .map(|severity| format!("-{}", severity.letter())),
+        );
+        Some(parts.join(" "))
+    }
+}
+
+/// How many neighbors of each filter match are shown, like grep's `-B`/`-A`.
+///
+/// Context lines are dimmed in the log list, and a divider marks where the
+/// displayed ranges are discontinuous.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FilterContext {
+    /// lines shown before each match
+    pub before: usize,
+    /// lines shown after each match
+    pub after: usize,
+}
+
+impl FilterContext {
+    /// steps of `FilterContext::cycle`
+    const STEPS: [usize; 5] = [1, 2, 3, 5, 10];
+
+    pub fn new(before: usize, after: usize) -> Self {
+        Self { before, after }
+    }
+
+    /// the same number of lines before and after (`-C`)
+    pub fn symmetric(lines: usize) -> Self {
+        Self::new(lines, lines)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.before > 0 || self.after > 0
+    }
+
+    /// next symmetric step (`0 -> 1 -> 2 -> 3 -> 5 -> 10 -> 0`)
+    pub fn cycle(self) -> Self {
+        let current = self.before.max(self.after);
+        Self::STEPS
+            .into_iter()
+            .find(|&lines| lines > current)
+            .map(Self::symmetric)
+            .unwrap_or_default()
+    }
+
+    /// compact description for the status bar, e.g. `context 2` or `context -B1 -A3`
+    pub fn indicator(&self) -> Option<String> {
+        if !self.is_active() {
+            None
+        } else if self.before == self.after {
+            Some(format!("context {}", self.before))
+        } else {
+            Some(format!("context -B{} -A{}", self.before, self.after))
+        }
+    }
+}
+
+/// A line emitted by [`ContextStream`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ContextLine<T> {
+    Match(T),
+    Context(T),
+    /// separates ranges that are not adjacent (grep's `--`)
+    Divider,
+}
+
+/// Grep-style context for a stream of items that cannot be revisited (headless mode).
+///
+/// Keeps the last `before` non-matching items until a match shows up, and lets
+/// `after` items through following each match.
+pub struct ContextStream<T> {
+    context: FilterContext,
+    /// candidate before-context with sequence numbers, oldest first
+    pending: VecDeque<(u64, T)>,
+    /// after-context lines still to emit
+    after_left: usize,
+    /// sequence number of the next pushed item
+    next_seq: u64,
+    /// sequence number of the last emitted item
+    last_emitted: Option<u64>,
+}
+
+impl<T> ContextStream<T> {
+    pub fn new(context: FilterContext) -> Self {
+        Self {
+            context,
+            pending: VecDeque::new(),
+            after_left: 0,
+            next_seq: 0,
+            last_emitted: None,
+        }
+    }
+
+    /// feed the next item, returns what should be printed now
+    pub fn push(&mut self, item: T, is_match: bool) -> Vec<ContextLine<T>> {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        let mut lines = Vec::new();
+        if is_match {
+            let first = self.pending.front().map_or(seq, |(first, _)| *first);
+            let is_gap = self.last_emitted.is_some_and(|last| last + 1 < first);
+            if self.context.is_active() && is_gap {
+                lines.push(ContextLine::Divider);
+            }
+            lines.extend(
+                self.pending
+                    .drain(..)
+                    .map(|(_, item)| ContextLine::Context(item)),
+            );
+            lines.push(ContextLine::Match(item));
+            self.after_left = self.context.after;
+            self.last_emitted = Some(seq);
+        } else if self.after_left > 0 {
+            self.after_left -= 1;
+            lines.push(ContextLine::Context(item));
+            self.last_emitted = Some(seq);
+        } else if self.context.before > 0 {
+            if self.pending.len() == self.context.before {
+                self.pending.pop_front();
+            }
+            self.pending.push_back((seq, item));
+        }
+        lines
+    }
+}
 
 /// filtering engine with incremental filtering and parallel processing
 pub struct FilterEngine {
@@ -8,6 +792,22 @@ pub struct FilterEngine {
     previous_query: String,
     /// cached results from previous filter
     previous_results: Vec<usize>,
+    /// the previous query, parsed
+    compiled: Option<FilterQuery>,
+    /// level filter the cached results were computed with
+    previous_levels: LevelFilter,
+    /// severity-based visibility, applied together with the query
+    levels: LevelFilter,
+    /// neighbors shown around query matches
+    context: FilterContext,
+    /// cached results plus their context, ascending
+    expanded: Vec<usize>,
+    /// after-context still owed to the last match in `expanded`
+    after_left: usize,
+    /// context `expanded` was built with, `None` when it must be rebuilt
+    expanded_with: Option<FilterContext>,
+    /// how queries are matched
+    mode: FilterMode,
     /// parser for converting log items to searchable text
     parser: Option<Arc<dyn LogParser>>,
 }
@@ -18,6 +818,14 @@ impl FilterEngine {
         Self {
             previous_query: String::new(),
             previous_results: Vec::new(),
+            compiled: None,
+            previous_levels: LevelFilter::default(),
+            levels: LevelFilter::default(),
+            context: FilterContext::default(),
+            expanded: Vec::new(),
+            after_left: 0,
+            expanded_with: None,
+            mode: FilterMode::default(),
             parser: None,
         }
     }
@@ -27,30 +835,91 @@ impl FilterEngine {
         self.parser = Some(parser);
     }
 
+    pub fn mode(&self) -> FilterMode {
+        self.mode
+    }
+
+    /// change the match mode, invalidating cached results
+    pub fn set_mode(&mut self, mode: FilterMode) {
+        if self.mode != mode {
+            self.mode = mode;
+            self.reset();
+        }
+    }
+
+    pub fn levels(&self) -> &LevelFilter {
+        &self.levels
+    }
+
+    /// change the level filter, the next `filter` call applies it
+    pub fn set_levels(&mut self, levels: LevelFilter) {
+        self.levels = levels;
+    }
+
+    pub fn context(&self) -> FilterContext {
+        self.context
+    }
+
+    /// change the context around matches, the next `filter` call applies it
+    pub fn set_context(&mut self, context: FilterContext) {
+        self.context = context;
+    }
+
+    /// whether the displayed item at `raw_idx` is only shown as context of a match
+    pub fn is_context_line(&self, raw_idx: usize) -> bool {
+        self.shows_context() && self.previous_results.binary_search(&raw_idx).is_err()
+    }
+
+    /// the last successfully applied query (used for highlighting)
+    pub fn query(&self) -> Option<&FilterQuery> {
+        self.compiled.as_ref()
+    }
+
+    /// last successfully applied query
+    pub fn active_query(&self) -> &str {
+        &self.previous_query
+    }
+
     /// filter logs and return indices of matching items
     ///
-    /// uses incremental filtering when possible (query extends previous query)
+    /// uses incremental filtering when possible (query narrows previous query)
     /// uses parallel processing for large search spaces
+    ///
+    /// returns an error if the query cannot be parsed, the cache is left untouched
     pub fn filter(
         &mut self,
         raw_logs: &[LogItem],
         query: &str,
         detail_level: LogDetailLevel,
-    ) -> Vec<usize> {
-        // empty query = show all
-        if query.is_empty() {
+    ) -> Result<Vec<usize>> {
+        // empty query and no level filter = show all
+        if query.is_empty() && !self.levels.is_active() {
             self.reset();
-            return (0..raw_logs.len()).collect();
+            return Ok((0..raw_logs.len()).collect());
         }
 
         // if no parser is set, return all items
         let Some(parser) = &self.parser else {
-            return (0..raw_logs.len()).collect();
+            return Ok((0..raw_logs.len()).collect());
         };
 
-        // check if we can use incremental filtering
-        let can_use_incremental = !self.previous_query.is_empty()
-            && query.starts_with(&self.previous_query)
+        let compiled = if query.is_empty() {
+            None
+        } else {
+            Some(FilterQuery::parse(query, self.mode)?)
+        };
+
+        // extending a query does not always narrow the result set
+        // (e.g. `a` -> `a OR b`, `-fo` -> `-foo`, regex `a` -> `a|b`)
+        let query_narrows = match (&compiled, &self.compiled) {
+            (Some(compiled), Some(previous)) => compiled.narrows(previous),
+            (_, None) => true,
+            (None, Some(_)) => false,
+        };
+        let has_previous = self.compiled.is_some() || self.previous_levels.is_active();
+        let can_use_incremental = has_previous
+            && query_narrows
+            && self.levels.narrows(&self.previous_levels)
             && !self.previous_results.is_empty();
 
         let search_space: Vec<usize> = if can_use_incremental {
@@ -61,15 +930,12 @@ impl FilterEngine {
             (0..raw_logs.len()).collect()
         };
 
-        // pre-lowercase the pattern once (not 50K times!)
-        let pattern_lower = query.to_lowercase();
-
         // use parallel filtering for large search spaces
         let filtered_indices = if search_space.len() > 1000 {
             self.filter_parallel(
                 raw_logs,
                 &search_space,
-                &pattern_lower,
+                compiled.as_ref(),
                 detail_level,
                 parser,
             )
@@ -77,7 +943,7 @@ impl FilterEngine {
             self.filter_sequential(
                 raw_logs,
                 &search_space,
-                &pattern_lower,
+                compiled.as_ref(),
                 detail_level,
                 parser,
             )
@@ -85,15 +951,37 @@ impl FilterEngine {
 
         // cache for next filter
         self.previous_query = query.to_string();
-        self.previous_results = filtered_indices.clone();
+        self.previous_results = filtered_indices;
+        self.previous_levels = self.levels.clone();
+        self.compiled = compiled;
+        self.expanded_with = None;
 
-        filtered_indices
+        Ok(self.with_context(raw_logs, 0))
+    }
+
+    /// drop the first `count` raw logs from the cached results, shifting the rest
+    ///
+    /// call this after removing the oldest items from the raw log buffer
+    pub fn evict_front(&mut self, count: usize) {
+        if count == 0 {
+            return;
+        }
+
+        for indices in [&mut self.previous_results, &mut self.expanded] {
+            indices.retain(|&idx| idx >= count);
+            for idx in indices.iter_mut() {
+                *idx -= count;
+            }
+        }
     }
 
     /// reset the filter cache
     pub fn reset(&mut self) {
         self.previous_query.clear();
         self.previous_results.clear();
+        self.previous_levels = LevelFilter::default();
+        self.compiled = None;
+        self.expanded_with = None;
     }
 
     /// filter only newly added logs and append to existing results
@@ -105,46 +993,129 @@ impl FilterEngine {
         old_count: usize,
         query: &str,
         detail_level: LogDetailLevel,
-    ) -> Vec<usize> {
-        // if query changed or no previous results, do full filter
-        if query != self.previous_query {
+    ) -> Result<Vec<usize>> {
+        // if query or levels changed, do full filter
+        if query != self.previous_query || self.levels != self.previous_levels {
             return self.filter(raw_logs, query, detail_level);
         }
 
         // if no new logs, return cached results
         if old_count >= raw_logs.len() {
-            return self.previous_results.clone();
+            return Ok(self.with_context(raw_logs, raw_logs.len()));
         }
 
-        // empty query = show all (including new ones)
-        if query.is_empty() {
-            return (0..raw_logs.len()).collect();
+        // empty query and no level filter = show all (including new ones)
+        if query.is_empty() && !self.levels.is_active() {
+            return Ok((0..raw_logs.len()).collect());
         }
 
         // if no parser is set, return all items
         let Some(parser) = &self.parser else {
-            return (0..raw_logs.len()).collect();
+            return Ok((0..raw_logs.len()).collect());
         };
 
+        if !query.is_empty() && self.compiled.is_none() {
+            return self.filter(raw_logs, query, detail_level);
+        }
+        let compiled = self.compiled.as_ref();
+
         // filter only the new logs
         let new_indices: Vec<usize> = (old_count..raw_logs.len()).collect();
-        let pattern_lower = query.to_lowercase();
 
         let new_filtered = if new_indices.len() > 1000 {
-            self.filter_parallel(raw_logs, &new_indices, &pattern_lower, detail_level, parser)
+            self.filter_parallel(raw_logs, &new_indices, compiled, detail_level, parser)
         } else {
-            self.filter_sequential(raw_logs, &new_indices, &pattern_lower, detail_level, parser)
+            self.filter_sequential(raw_logs, &new_indices, compiled, detail_level, parser)
         };
 
         // append new filtered indices to existing results
-        let mut all_results = self.previous_results.clone();
-        all_results.extend(new_filtered);
-
-        // update cache
-        self.previous_results = all_results.clone();
+        self.previous_results.extend(new_filtered);
         // query stays the same, so previous_query doesn't need updating
 
-        all_results
+        // new logs may also be after-context of earlier matches
+        Ok(self.with_context(raw_logs, old_count))
+    }
+
+    /// whether context lines are being added
+    ///
+    /// context is only added around text query matches, a level filter alone gets none
+    pub fn shows_context(&self) -> bool {
+        self.context.is_active() && self.compiled.is_some()
+    }
+
+    /// the cached matches plus their neighbors, sorted
+    ///
+    /// the expansion is cached, only logs from `from` on are added to it, unless the
+    /// context changed since
+    fn with_context(&mut self, raw_logs: &[LogItem], from: usize) -> Vec<usize> {
+        if !self.shows_context() {
+            return self.previous_results.clone();
+        }
+
+        let from = if self.expanded_with == Some(self.context) {
+            from
+        } else {
+            self.expanded.clear();
+            self.after_left = 0;
+            self.expanded_with = Some(self.context);
+            0
+        };
+        self.expand_context(raw_logs, from);
+        self.expanded.clone()
+    }
+
+    /// add the matches from `from` on and their neighbors to `expanded`
+    ///
+    /// neighbors hidden by the level filter are skipped, not counted. Everything added
+    /// lies past the last expanded log, as the before-context of a new match that is
+    /// not past it is already shown with the earlier match
+    fn expand_context(&mut self, raw_logs: &[LogItem], from: usize) {
+        let Some(parser) = &self.parser else {
+            return;
+        };
+        let levels = &self.levels;
+        let visible = |idx: &usize| levels.allows(parser.severity(&raw_logs[*idx]));
+
+        let first_new = self.previous_results.partition_point(|&idx| idx < from);
+        let mut cursor = from;
+        for &idx in &self.previous_results[first_new..] {
+            // after-context of the previous match, up to this one
+            while self.after_left > 0 && cursor < idx {
+                if visible(&cursor) {
+                    self.expanded.push(cursor);
+                    self.after_left -= 1;
+                }
+                cursor += 1;
+            }
+
+            let floor = self.expanded.last().map_or(0, |&last| last + 1);
+            let start = self.expanded.len();
+            self.expanded
+                .extend((floor..idx).rev().filter(visible).take(self.context.before));
+            self.expanded[start..].reverse();
+            self.expanded.push(idx);
+            self.after_left = self.context.after;
+            cursor = idx + 1;
+        }
+
+        while self.after_left > 0 && cursor < raw_logs.len() {
+            if visible(&cursor) {
+                self.expanded.push(cursor);
+                self.after_left -= 1;
+            }
+            cursor += 1;
+        }
+    }
+
+    fn item_matches(
+        &self,
+        item: &LogItem,
+        query: Option<&FilterQuery>,
+        detail_level: LogDetailLevel,
+        parser: &Arc<dyn LogParser>,
+    ) -> bool {
+        self.levels.allows(parser.severity(item))
+            && query.is_none_or(|query| query.matches(parser.as_ref(), item, detail_level))
     }
 
     /// sequential filtering (for small search spaces)
@@ -152,19 +1123,13 @@ impl FilterEngine {
         &self,
         raw_logs: &[LogItem],
         search_space: &[usize],
-        pattern_lower: &str,
+        query: Option<&FilterQuery>,
         detail_level: LogDetailLevel,
         parser: &Arc<dyn LogParser>,
     ) -> Vec<usize> {
         search_space
             .iter()
-            .filter(|&&idx| {
-                let item = &raw_logs[idx];
-                parser
-                    .get_searchable_text(item, detail_level)
-                    .to_lowercase()
-                    .contains(pattern_lower)
-            })
+            .filter(|&&idx| self.item_matches(&raw_logs[idx], query, detail_level, parser))
             .copied()
             .collect()
     }
@@ -174,19 +1139,13 @@ impl FilterEngine {
         &self,
         raw_logs: &[LogItem],
         search_space: &[usize],
-        pattern_lower: &str,
+        query: Option<&FilterQuery>,
         detail_level: LogDetailLevel,
         parser: &Arc<dyn LogParser>,
     ) -> Vec<usize> {
         search_space
             .par_iter()
-            .filter(|&&idx| {
-                let item = &raw_logs[idx];
-                parser
-                    .get_searchable_text(item, detail_level)
-                    .to_lowercase()
-                    .contains(pattern_lower)
-            })
+            .filter(|&&idx| self.item_matches(&raw_logs[idx], query, detail_level, parser))
             .copied()
             .collect()
     }
@@ -197,3 +1156,416 @@ impl Default for FilterEngine {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ContentParser;
+
+    impl LogParser for ContentParser {
+        fn parse(&self, raw_log: &str) -> Option<LogItem> {
+            Some(LogItem::new(raw_log.to_string(), raw_log.to_string()))
+        }
+
+        fn format_preview(&self, item: &LogItem, _detail_level: LogDetailLevel) -> String {
+            item.content.clone()
+        }
+
+        fn get_searchable_text(&self, item: &LogItem, _detail_level: LogDetailLevel) -> String {
+            item.content.clone()
+        }
+    }
+
+    fn logs(lines: &[&str]) -> Vec<LogItem> {
+        lines
+            .iter()
+            .map(|line| LogItem::new(line.to_string(), line.to_string()))
+            .collect()
+    }
+
+    fn engine(mode: FilterMode) -> FilterEngine {
+        let mut engine = FilterEngine::new();
+        engine.set_formatter(Arc::new(ContentParser));
+        engine.set_mode(mode);
+        engine
+    }
+
+    #[test]
+    fn test_literal_mode_ignores_case() {
+        let raw = logs(&["Error: boom", "info", "an ERROR"]);
+        let mut engine = engine(FilterMode::Literal);
+        assert_eq!(engine.filter(&raw, "error", 0).unwrap(), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_case_sensitive_mode() {
+        let raw = logs(&["Error: boom", "info", "an ERROR"]);
+        let mut engine = engine(FilterMode::CaseSensitive);
+        assert_eq!(engine.filter(&raw, "ERROR", 0).unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn test_literal_mode_escapes_regex_syntax() {
+        let raw = logs(&["a.b", "axb"]);
+        let mut engine = engine(FilterMode::Literal);
+        assert_eq!(engine.filter(&raw, "a.b", 0).unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn test_regex_mode() {
+        let raw = logs(&["frame 12 ready", "frame ready", "frame 7 ready"]);
+        let mut engine = engine(FilterMode::Regex);
+        assert_eq!(engine.filter(&raw, r"frame \d+", 0).unwrap(), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_regex_extension_does_not_use_incremental_results() {
+        let raw = logs(&["alpha", "beta", "gamma"]);
+        let mut engine = engine(FilterMode::Regex);
+        assert_eq!(engine.filter(&raw, "alpha", 0).unwrap(), vec![0]);
+        // `alpha|beta` extends `alpha` but widens the result set
+        assert_eq!(engine.filter(&raw, "alpha|beta", 0).unwrap(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_invalid_regex_reports_error_and_keeps_cache() {
+        let raw = logs(&["foo(bar", "foo"]);
+        let mut engine = engine(FilterMode::Regex);
+        assert_eq!(engine.filter(&raw, "foo", 0).unwrap(), vec![0, 1]);
+
+        let err = engine.filter(&raw, "foo(", 0).unwrap_err();
+        assert!(err.to_string().starts_with("invalid regex:"));
+        assert!(!err.to_string().contains('\n'));
+        assert_eq!(engine.active_query(), "foo");
+    }
+
+    #[test]
+    fn test_mode_change_resets_cache() {
+        let raw = logs(&["Foo", "foo"]);
+        let mut engine = engine(FilterMode::CaseSensitive);
+        assert_eq!(engine.filter(&raw, "foo", 0).unwrap(), vec![1]);
+
+        engine.set_mode(FilterMode::Literal);
+        assert_eq!(engine.filter(&raw, "foo", 0).unwrap(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_filter_new_logs_appends_matches() {
+        let mut raw = logs(&["error one", "info"]);
+        let mut engine = engine(FilterMode::Literal);
+        assert_eq!(engine.filter(&raw, "error", 0).unwrap(), vec![0]);
+
+        raw.extend(logs(&["error two"]));
+        assert_eq!(
+            engine.filter_new_logs(&raw, 2, "error", 0).unwrap(),
+            vec![0, 2]
+        );
+    }
+
+    #[test]
+    fn test_matcher_find_ranges_skips_empty_matches() {
+        let matcher = Matcher::new("x*", FilterMode::Regex).unwrap();
+        assert_eq!(matcher.find_ranges("abxxc"), vec![(2, 4)]);
+    }
+
+    fn item(content: &str, level: &str, tag: &str) -> LogItem {
+        LogItem::new(content.to_string(), content.to_string())
+            .with_metadata("level", level)
+            .with_metadata("tag", tag)
+    }
+
+    fn query_matches(query: &str, item: &LogItem) -> bool {
+        FilterQuery::parse(query, FilterMode::Literal)
+            .unwrap()
+            .matches(&ContentParser, item, 0)
+    }
+
+    #[test]
+    fn test_query_terms_are_anded() {
+        let log = item("render frame took 20ms", "INFO", "Effect");
+        assert!(query_matches("frame 20ms", &log));
+        assert!(query_matches("frame AND 20ms", &log));
+        assert!(!query_matches("frame timeout", &log));
+    }
+
+    #[test]
+    fn test_query_qualifiers_and_negation() {
+        let error = item("load failed: timeout", "ERROR", "Effect");
+        let other = item("load failed: missing file", "ERROR", "Effect");
+        let info = item("load ok", "INFO", "Effect");
+
+        let query = "level:error tag:Effect -timeout";
+        assert!(!query_matches(query, &error));
+        assert!(query_matches(query, &other));
+        assert!(!query_matches(query, &info));
+    }
+
+    #[test]
+    fn test_query_or_not_and_grouping() {
+        let warn = item("slow frame", "WARN", "Render");
+        let error = item("crash", "ERROR", "Render");
+        let info = item("slow frame", "INFO", "Audio");
+
+        let query = "(level:warn OR level:error) NOT tag:audio";
+        assert!(query_matches(query, &warn));
+        assert!(query_matches(query, &error));
+        assert!(!query_matches(query, &info));
+    }
+
+    #[test]
+    fn test_query_negated_group() {
+        let timeout = item("load failed: timeout", "ERROR", "Net");
+        let crash = item("crash in render", "ERROR", "Render");
+        let ok = item("load ok", "INFO", "Net");
+
+        let query = "-(timeout OR crash)";
+        assert!(!query_matches(query, &timeout));
+        assert!(!query_matches(query, &crash));
+        assert!(query_matches(query, &ok));
+        assert!(query_matches("tag:net -(timeout)", &ok));
+    }
+
+    #[test]
+    fn test_query_level_matches_by_severity() {
+        let android_error = item("boom", "E", "ActivityManager");
+        let android_warn = item("slow", "W", "ActivityManager");
+        let ios_error = item("boom", "Error", "SpringBoard");
+        let ios_fault = item("crash", "Fault", "SpringBoard");
+        let system = item("started", "SYSTEM", "dyeh");
+
+        assert!(query_matches("level:error", &android_error));
+        assert!(query_matches("level:error", &ios_error));
+        assert!(!query_matches("level:error", &android_warn));
+        assert!(!query_matches("level:error", &ios_fault));
+        assert!(query_matches("level:warn", &android_warn));
+        assert!(query_matches("level:fatal", &ios_fault));
+        assert!(query_matches("level:fault", &ios_fault));
+        // `e` is the logcat letter for errors, not a substring of `SYSTEM`
+        assert!(query_matches("level:e", &ios_error));
+        assert!(!query_matches("level:e", &system));
+        // values that are not a level keep matching the raw string
+        assert!(query_matches("level:sys", &system));
+    }
+
+    #[test]
+    fn test_query_quoted_phrases() {
+        let log = item("connection reset by peer", "ERROR", "Net");
+        assert!(query_matches("\"reset by\"", &log));
+        assert!(!query_matches("\"by reset\"", &log));
+        assert!(!query_matches("-\"reset by\"", &log));
+        assert!(query_matches("tag:\"net\"", &log));
+    }
+
+    #[test]
+    fn test_query_unknown_key_matches_as_text() {
+        let log = item("GET http://example.com at 12:30:45", "INFO", "Net");
+        assert!(query_matches("http://example.com", &log));
+        assert!(query_matches("12:30", &log));
+    }
+
+    #[test]
+    fn test_query_parens_inside_words_are_literal() {
+        let log = item("called init() twice", "INFO", "App");
+        assert!(query_matches("init()", &log));
+        assert!(query_matches("(twice OR init())", &log));
+        assert!(FilterQuery::parse("foo )", FilterMode::Literal).is_err());
+    }
+
+    #[test]
+    fn test_query_narrows() {
+        let parse = |query| FilterQuery::parse(query, FilterMode::Literal).unwrap();
+        assert!(parse("error").narrows(&parse("err")));
+        assert!(parse("level:error foo").narrows(&parse("level:err")));
+        assert!(!parse("err OR foo").narrows(&parse("err")));
+        assert!(!parse("-timeout").narrows(&parse("-time")));
+        assert!(!parse("level:error").narrows(&parse("level")));
+        assert!(parse("level:E").narrows(&parse("level:error")));
+        assert!(!parse("level:er").narrows(&parse("level:e")));
+    }
+
+    #[test]
+    fn test_engine_uses_query_language() {
+        let raw = vec![
+            item("alpha", "ERROR", "A"),
+            item("beta", "INFO", "B"),
+            item("gamma", "ERROR", "B"),
+        ];
+        let mut engine = engine(FilterMode::Literal);
+        assert_eq!(engine.filter(&raw, "level:error", 0).unwrap(), vec![0, 2]);
+        // `OR` extends the previous query but widens the result set
+        assert_eq!(
+            engine.filter(&raw, "level:error OR beta", 0).unwrap(),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn test_highlight_ranges_skip_negated_and_qualified_terms() {
+        let query = FilterQuery::parse("foo level:error -bar", FilterMode::Literal).unwrap();
+        assert_eq!(query.highlight_ranges("foo bar foo"), vec![(0, 3), (8, 11)]);
+    }
+
+    #[test]
+    fn test_evict_front_shifts_cached_results() {
+        let mut raw = logs(&["err a", "ok", "err b", "err c"]);
+        let mut engine = engine(FilterMode::Literal);
+        assert_eq!(engine.filter(&raw, "err", 0).unwrap(), vec![0, 2, 3]);
+
+        raw.drain(..2);
+        engine.evict_front(2);
+        raw.extend(logs(&["err d"]));
+        assert_eq!(
+            engine.filter_new_logs(&raw, 2, "err", 0).unwrap(),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn test_level_filter_combines_with_query() {
+        let raw = vec![
+            item("net up", "V", "net"),
+            item("net slow", "W", "net"),
+            item("disk full", "E", "disk"),
+            item("net down", "E", "net"),
+            item("net unknown", "", "net"),
+        ];
+        let mut engine = engine(FilterMode::Literal);
+
+        let mut levels = LevelFilter::default();
+        levels.cycle_min_level(); // >= debug
+        levels.cycle_min_level(); // >= info
+        engine.set_levels(levels.clone());
+        // items without a known severity stay visible
+        assert_eq!(engine.filter(&raw, "", 0).unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(engine.filter(&raw, "net", 0).unwrap(), vec![1, 3, 4]);
+
+        levels.toggle(Severity::Error);
+        assert_eq!(levels.indicator().as_deref(), Some("level>=I -E"));
+        engine.set_levels(levels);
+        assert_eq!(engine.filter(&raw, "net", 0).unwrap(), vec![1, 4]);
+
+        // showing levels again widens the results, the cache must not be reused
+        engine.set_levels(LevelFilter::default());
+        assert_eq!(engine.filter(&raw, "net", 0).unwrap(), vec![0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn test_min_level_cycles_back_to_off() {
+        let mut levels = LevelFilter::default();
+        let mut seen = Vec::new();
+        for _ in 0..7 {
+            levels.cycle_min_level();
+            seen.push(levels.min_level());
+        }
+        assert_eq!(seen[0], Some(Severity::Debug));
+        assert_eq!(seen[5], Some(Severity::Fatal));
+        assert_eq!(seen[6], None);
+        assert!(!levels.is_active());
+    }
+
+    #[test]
+    fn test_context_adds_neighbors_and_follows_new_logs() {
+        let mut raw = logs(&["a", "b", "crash", "at foo", "at bar", "c", "d", "e"]);
+        let mut engine = engine(FilterMode::Literal);
+        engine.set_context(FilterContext::new(1, 2));
+
+        assert_eq!(engine.filter(&raw, "crash", 0).unwrap(), vec![1, 2, 3, 4]);
+        assert!(engine.is_context_line(1));
+        assert!(!engine.is_context_line(2));
+
+        // a match near the end gets its after-context once it arrives
+        raw.extend(logs(&["crash again"]));
+        assert_eq!(
+            engine.filter_new_logs(&raw, 8, "crash", 0).unwrap(),
+            vec![1, 2, 3, 4, 7, 8]
+        );
+        raw.extend(logs(&["at baz"]));
+        assert_eq!(
+            engine.filter_new_logs(&raw, 9, "crash", 0).unwrap(),
+            vec![1, 2, 3, 4, 7, 8, 9]
+        );
+    }
+
+    #[test]
+    fn test_incremental_context_matches_full_expansion() {
+        let lines = [
+            "crash", "a", "b", "c", "crash", "d", "e", "f", "g", "h", "crash", "crash", "i",
+        ];
+        let mut engine = engine(FilterMode::Literal);
+        engine.set_context(FilterContext::new(2, 1));
+
+        // one log per batch, the last batch is compared with a fresh expansion
+        let mut raw = Vec::new();
+        let mut incremental = engine.filter(&raw, "crash", 0).unwrap();
+        for (count, line) in lines.iter().enumerate() {
+            raw.extend(logs(&[line]));
+            incremental = engine.filter_new_logs(&raw, count, "crash", 0).unwrap();
+        }
+
+        let mut fresh = self::engine(FilterMode::Literal);
+        fresh.set_context(FilterContext::new(2, 1));
+        let full = fresh.filter(&raw, "crash", 0).unwrap();
+        assert_eq!(incremental, full);
+        assert_eq!(full, vec![0, 1, 2, 3, 4, 5, 8, 9, 10, 11, 12]);
+    }
+
+    #[test]
+    fn test_context_skips_items_hidden_by_level() {
+        let raw = vec![
+            item("boot", "I", ""),
+            item("spam", "V", ""),
+            item("crash", "E", ""),
+            item("spam", "V", ""),
+            item("at foo", "E", ""),
+        ];
+        let mut engine = engine(FilterMode::Literal);
+        let mut levels = LevelFilter::default();
+        levels.toggle(Severity::Verbose);
+        engine.set_levels(levels);
+        engine.set_context(FilterContext::symmetric(1));
+
+        assert_eq!(engine.filter(&raw, "crash", 0).unwrap(), vec![0, 2, 4]);
+        // a level filter alone gets no context
+        assert_eq!(engine.filter(&raw, "", 0).unwrap(), vec![0, 2, 4]);
+        assert!(!engine.is_context_line(0));
+    }
+
+    #[test]
+    fn test_context_stream_emits_dividers_between_ranges() {
+        let mut stream = ContextStream::new(FilterContext::new(1, 1));
+        let lines: Vec<_> = ["a", "b", "hit", "c", "d", "e", "hit", "hit", "f"]
+            .into_iter()
+            .flat_map(|line| stream.push(line, line == "hit"))
+            .collect();
+
+        use ContextLine::*;
+        assert_eq!(
+            lines,
+            vec![
+                Context("b"),
+                Match("hit"),
+                Context("c"),
+                Divider,
+                Context("e"),
+                Match("hit"),
+                Match("hit"),
+                Context("f"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_context_cycles_back_to_off() {
+        let mut context = FilterContext::new(0, 4);
+        context = context.cycle();
+        assert_eq!(context, FilterContext::symmetric(5));
+        assert_eq!(context.indicator().as_deref(), Some("context 5"));
+        context = context.cycle().cycle();
+        assert!(!context.is_active());
+        assert_eq!(
+            FilterContext::new(1, 3).indicator().as_deref(),
+            Some("context -B1 -A3")
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use crate::theme::Theme;
+use ratatui::prelude::*;
+use serde_json::Value;
+use std::{collections::HashSet, ops::Range};
+use unicode_width::UnicodeWidthStr;
+
+/// a piece of log content, JSON payloads are pretty-printed in the details pane
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ContentSegment {
+    Text(String),
+    Json(Value),
+}
+
+/// what a click on a column range of a details line does
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ClickAction {
+    /// a double-click copies the text
+    Copy(String),
+    /// a click folds or unfolds the JSON container at the path
+    Fold(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ClickTarget {
+    pub columns: Range<usize>,
+    pub action: ClickAction,
+}
+
+/// a rendered details line and the click targets on it
+pub(crate) type DetailsLine = (Line<'static>, Vec<ClickTarget>);
+
+/// splits content into text and the JSON objects and arrays embedded in it
+pub(crate) fn split_json(content: &str) -> Vec<ContentSegment> {
+    let mut segments = Vec::new();
+    let mut text_start = 0;
+    let mut search_from = 0;
+
+    while let Some(offset) = content[search_from..].find(['{', '[']) {
+        let start = search_from + offset;
+        match parse_payload(&content[start..]) {
+            Some((value, len)) => {
+                if text_start < start {
+                    segments.push(ContentSegment::Text(content[text_start..start].to_string()));
+                }
+                segments.push(ContentSegment::Json(value));
+                text_start = start + len;
+                search_from = text_start;
+            }
+            None => search_from = start + 1,
+        }
+    }
+
+    if text_start < content.len() {
+        segments.push(ContentSegment::Text(content[text_start..].to_string()));
+    }
+    segments
+}
+
+/// the JSON value at the start of `text` and its length in bytes, if it is worth
+/// pretty-printing: bracketed tags like `[42]` or `[main]` stay inline
+fn parse_payload(text: &str) -> Option<(Value, usize)> {
+    let mut stream = serde_json::Deserializer::from_str(text).into_iter::<Value>();
+    let value = stream.next()?.ok()?;
+    let worth_it = match &value {
+        Value::Object(map) => !map.is_empty(),
+        Value::Array(items) => items.iter().any(|item| item.is_object() || item.is_array()),
+        _ => false,
+    };
+    worth_it.then(|| (value, stream.byte_offset()))
+}
+
+/// pretty-printed, syntax-colored lines of a JSON value
+///
+/// non-empty containers get a fold marker in front of their key, those at a path in
+/// `folds` are collapsed to one line. `root` prefixes the paths, to tell several payloads
+/// apart.
+pub(crate) fn json_lines(
+    value: &Value,
+    root: &str,
+    folds: &HashSet<String>,
+    theme: &Theme,
+) -> Vec<DetailsLine> {
+    let mut printer = JsonPrinter {
+        folds,
+        theme,
+        lines: Vec::new(),
+    };
+    printer.write_value(None, value, root.to_string(), 0, false);
+    printer.lines
+}
+
+/// paths of the nested containers of a JSON value, these are folded by "collapse all"
+pub(crate) fn nested_fold_paths(value: &Value, root: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    collect_fold_paths(value, root, &mut paths);
+    paths
+}
+
+fn collect_fold_paths(value: &Value, path: &str, paths: &mut Vec<String>) {
+    for (key, child) in children(value) {
+        if is_foldable(child) {
+            let child_path = child_path(path, &key);
+            collect_fold_paths(child, &child_path, paths);
+            paths.push(child_path);
+        }
+    }
+}
+
+fn is_foldable(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => !map.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+/// members of an object by key, elements of an array by index
+fn children(value: &Value) -> Vec<(String, &Value)> {
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, child)| (key.clone(), child))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, child)| (index.to_string(), child))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// JSON pointer style, so keys containing `/` cannot collide
+fn child_path(path: &str, key: &str) -> String {
+    format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
+
+/// text a double-click on a value copies: strings without quotes, containers compact
+fn copy_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+struct JsonPrinter<'a> {
+    folds: &'a HashSet<String>,
+    theme: &'a Theme,
+    lines: Vec<DetailsLine>,
+}
+
+/// a line being built, tracking the display column of each span
+#[derive(Default)]
+struct LineBuilder {
+    spans: Vec<Span<'static>>,
+    targets: Vec<ClickTarget>,
+    width: usize,
+}
+
+impl LineBuilder {
+    fn push(&mut self, text: impl Into<String>, style: Style) -> Range<usize> {
+        let text = text.into();
+        let start = self.width;
+        self.width += text.width();
+        self.spans.push(Span::styled(text, style));
+        start..self.width
+    }
+
+    fn push_target(&mut self, text: impl Into<String>, style: Style, action: ClickAction) {
+        let columns = self.push(text, style);
+        self.targets.push(ClickTarget { columns, action });
+    }
+
+    fn finish(self) -> DetailsLine {
+        (Line::from(self.spans), self.targets)
+    }
+}
+
+impl JsonPrinter<'_> {
+    fn write_value(
+        &mut self,
+        key: Option<&str>,
+        value: &Value,
+        path: String,
+        depth: usize,
+        trailing_comma: bool,
+    ) {
+        let mut line = LineBuilder::default();
+        let foldable = is_foldable(value);
+        let folded = foldable && self.folds.contains(&path);
+
+        line.push("  ".repeat(depth), Style::new());
+        if foldable {
+            let marker = if folded { "▸" } else { "▾" };
+            line.push_target(marker, self.theme.context, ClickAction::Fold(path.clone()));
+            line.push(" ", Style::new());
+        } else {
+            line.push("  ", Style::new());
+        }
+
+        if let Some(key) = key {
+            let quoted = Value::String(key.to_string()).to_string();
+            line.push_target(
+                quoted,
+                self.theme.json_key,
+                ClickAction::Copy(key.to_string()),
+            );
+            line.push(": ", Style::new());
+        }
+
+        let comma = if trailing_comma { "," } else { "" };
+        let (open, close, count) = match value {
+            Value::Object(map) => ("{", "}", format!("{} keys", map.len())),
+            Value::Array(items) => ("[", "]", format!("{} items", items.len())),
+            scalar => {
+                let style = match scalar {
+                    Value::String(_) => self.theme.json_string,
+                    Value::Number(_) => self.theme.json_number,
+                    _ => self.theme.json_literal,
+                };
+                line.push_target(
+                    scalar.to_string(),
+                    style,
+                    ClickAction::Copy(copy_text(scalar)),
+                );
+                line.push(comma, Style::new());
+                self.lines.push(line.finish());
+                return;
+            }
+        };
+
+        let copy = ClickAction::Copy(copy_text(value));
+        if !foldable {
+            line.push_target(format!("{}{}", open, close), Style::new(), copy);
+            line.push(comma, Style::new());
+            self.lines.push(line.finish());
+            return;
+        }
+        if folded {
+            // `{ … 3 keys }`, copied as a whole
+            let start = line.push(open, Style::new()).start;
+            line.push(format!(" … {} ", count), self.theme.context);
+            let end = line.push(close, Style::new()).end;
+            line.targets.push(ClickTarget {
+                columns: start..end,
+                action: copy,
+            });
+            line.push(comma, Style::new());
+            self.lines.push(line.finish());
+            return;
+        }
+
+        line.push_target(open, Style::new(), copy);
+        self.lines.push(line.finish());
+
+        let children = children(value);
+        let last = children.len().saturating_sub(1);
+        let is_object = value.is_object();
+        for (index, (child_key, child)) in children.into_iter().enumerate() {
+            let path = child_path(&path, &child_key);
+            let key = is_object.then_some(child_key.as_str());
+            self.write_value(key, child, path, depth + 1, index < last);
+        }
+
+        let mut line = LineBuilder::default();
+        line.push(
+            format!("{}  {}{}", "  ".repeat(depth), close, comma),
+            Style::new(),
+        );
+        self.lines.push(line.finish());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn texts(lines: &[DetailsLine]) -> Vec<String> {
+        lines.iter().map(|(line, _)| line.to_string()).collect()
+    }
+
+    #[test]
+    fn test_split_json_finds_embedded_payloads() {
+        let segments = split_json(r#"effect loaded [main] {"id": 3, "tags": ["a"]} took 4ms"#);
+        assert_eq!(
+            segments,
+            vec![
+                ContentSegment::Text("effect loaded [main] ".to_string()),
+                ContentSegment::Json(json!({"id": 3, "tags": ["a"]})),
+                ContentSegment::Text(" took 4ms".to_string()),
+            ]
+        );
+
+        // tags, empty objects and broken payloads stay text
+        for text in ["[42] done", "state {} ok", r#"{"id": 3"#, "plain"] {
+            assert_eq!(
+                split_json(text),
+                vec![ContentSegment::Text(text.to_string())]
+            );
+        }
+        assert_eq!(
+            split_json(r#"[{"a": 1}]"#),
+            vec![ContentSegment::Json(json!([{"a": 1}]))]
+        );
+    }
+
+    #[test]
+    fn test_json_lines_pretty_print_and_fold() {
+        let value: Value =
+            serde_json::from_str(r#"{"name": "blur", "params": {"radius": 2, "on": true}}"#)
+                .unwrap();
+        let theme = Theme::dark();
+
+        let lines = json_lines(&value, "0", &HashSet::new(), &theme);
+        assert_eq!(
+            texts(&lines),
+            vec![
+                "▾ {",
+                "    \"name\": \"blur\",",
+                "  ▾ \"params\": {",
+                "      \"radius\": 2,",
+                "      \"on\": true",
+                "    }",
+                "  }",
+            ]
+        );
+
+        // values are copied without quotes, keys by name
+        let targets = &lines[1].1;
+        assert_eq!(targets[0].action, ClickAction::Copy("name".to_string()));
+        assert_eq!(targets[0].columns, 4..10);
+        assert_eq!(targets[1].action, ClickAction::Copy("blur".to_string()));
+        assert_eq!(targets[1].columns, 12..18);
+        assert_eq!(
+            lines[2].1[0].action,
+            ClickAction::Fold("0/params".to_string())
+        );
+
+        let folds = nested_fold_paths(&value, "0").into_iter().collect();
+        let lines = json_lines(&value, "0", &folds, &theme);
+        assert_eq!(
+            texts(&lines),
+            vec![
+                "▾ {",
+                "    \"name\": \"blur\",",
+                "  ▸ \"params\": { … 2 keys }",
+                "  }"
+            ]
+        );
+        assert_eq!(
+            lines[2].1.last().unwrap().action,
+            ClickAction::Copy(r#"{"radius":2,"on":true}"#.to_string())
+        );
+    }
+}
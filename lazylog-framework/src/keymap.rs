@@ -0,0 +1,657 @@
+use anyhow::{Result, anyhow, bail};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::{fmt, str::FromStr};
+
+/// Something a key binding does, see [`Keymap`].
+///
+/// Text inputs (filter, search, export prompt) and the key after the level toggle
+/// read keys directly and are not remappable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    /// exit visual mode, clear the search or the filter
+    Back,
+    NextLog,
+    PrevLog,
+    ScrollLeft,
+    ScrollRight,
+    JumpToTop,
+    JumpToBottom,
+    RevealSelection,
+    Filter,
+    Search,
+    NextMatch,
+    PrevMatch,
+    VisualMode,
+    Yank,
+    YankAll,
+    Export,
+    Clear,
+    ToggleWrap,
+    /// collapse every nested JSON container in the details pane, or expand them again
+    ToggleFolds,
+    ToggleMouseCapture,
+    ToggleDebugLogs,
+    CycleMinLevel,
+    /// the next key names the level to hide or show
+    ToggleLevel,
+    CycleContext,
+    ToggleBookmark,
+    BookmarksOnly,
+    NextBookmark,
+    PrevBookmark,
+    DecreaseDetail,
+    IncreaseDetail,
+    FocusLogs,
+    FocusDetails,
+    FocusDebug,
+    Help,
+    NextTab,
+    PrevTab,
+    OpenTab,
+    CloseTab,
+}
+
+impl Action {
+    pub const ALL: [Action; 39] = [
+        Action::Quit,
+        Action::Back,
+        Action::NextLog,
+        Action::PrevLog,
+        Action::ScrollLeft,
+        Action::ScrollRight,
+        Action::JumpToTop,
+        Action::JumpToBottom,
+        Action::RevealSelection,
+        Action::Filter,
+        Action::Search,
+        Action::NextMatch,
+        Action::PrevMatch,
+        Action::VisualMode,
+        Action::Yank,
+        Action::YankAll,
+        Action::Export,
+        Action::Clear,
+        Action::ToggleWrap,
+        Action::ToggleFolds,
+        Action::ToggleMouseCapture,
+        Action::ToggleDebugLogs,
+        Action::CycleMinLevel,
+        Action::ToggleLevel,
+        Action::CycleContext,
+        Action::ToggleBookmark,
+        Action::BookmarksOnly,
+        Action::NextBookmark,
+        Action::PrevBookmark,
+        Action::DecreaseDetail,
+        Action::IncreaseDetail,
+        Action::FocusLogs,
+        Action::FocusDetails,
+        Action::FocusDebug,
+        Action::Help,
+        Action::NextTab,
+        Action::PrevTab,
+        Action::OpenTab,
+        Action::CloseTab,
+    ];
+
+    /// kebab-case name, as used in config files
+    pub fn name(self) -> &'static str {
+        use Action::*;
+        match self {
+            Quit => "quit",
+            Back => "back",
+            NextLog => "next-log",
+            PrevLog => "prev-log",
+            ScrollLeft => "scroll-left",
+            ScrollRight => "scroll-right",
+            JumpToTop => "jump-to-top",
+            JumpToBottom => "jump-to-bottom",
+            RevealSelection => "reveal-selection",
+            Filter => "filter",
+            Search => "search",
+            NextMatch => "next-match",
+            PrevMatch => "prev-match",
+            VisualMode => "visual-mode",
+            Yank => "yank",
+            YankAll => "yank-all",
+            Export => "export",
+            Clear => "clear",
+            ToggleWrap => "toggle-wrap",
+            ToggleFolds => "toggle-folds",
+            ToggleMouseCapture => "toggle-mouse-capture",
+            ToggleDebugLogs => "toggle-debug-logs",
+            CycleMinLevel => "cycle-min-level",
+            ToggleLevel => "toggle-level",
+            CycleContext => "cycle-context",
+            ToggleBookmark => "toggle-bookmark",
+            BookmarksOnly => "bookmarks-only",
+            NextBookmark => "next-bookmark",
+            PrevBookmark => "prev-bookmark",
+            DecreaseDetail => "decrease-detail",
+            IncreaseDetail => "increase-detail",
+            FocusLogs => "focus-logs",
+            FocusDetails => "focus-details",
+            FocusDebug => "focus-debug",
+            Help => "help",
+            NextTab => "next-tab",
+            PrevTab => "prev-tab",
+            OpenTab => "open-tab",
+            CloseTab => "close-tab",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+/// the built-in bindings, see [`Keymap::default`]
+const DEFAULT_BINDINGS: &[(Action, &[&str])] = &[
+    (Action::Quit, &["q", "ctrl-c"]),
+    (Action::Back, &["esc"]),
+    (Action::NextLog, &["j", "down"]),
+    (Action::PrevLog, &["k", "up"]),
+    (Action::ScrollLeft, &["h", "left"]),
+    (Action::ScrollRight, &["l", "right"]),
+    (Action::JumpToTop, &["gg"]),
+    (Action::JumpToBottom, &["d", "G"]),
+    (Action::RevealSelection, &["space"]),
+    (Action::Filter, &["/", "f"]),
+    (Action::Search, &["s"]),
+    (Action::NextMatch, &["n"]),
+    (Action::PrevMatch, &["N"]),
+    (Action::VisualMode, &["v"]),
+    (Action::Yank, &["y"]),
+    (Action::YankAll, &["a"]),
+    (Action::Export, &["e"]),
+    (Action::Clear, &["c"]),
+    (Action::ToggleWrap, &["w"]),
+    (Action::ToggleFolds, &["z"]),
+    (Action::ToggleMouseCapture, &["m"]),
+    (Action::ToggleDebugLogs, &["b"]),
+    (Action::CycleMinLevel, &["L"]),
+    (Action::ToggleLevel, &["t"]),
+    (Action::CycleContext, &["C"]),
+    (Action::ToggleBookmark, &["x"]),
+    (Action::BookmarksOnly, &["X"]),
+    (Action::NextBookmark, &["}"]),
+    (Action::PrevBookmark, &["{"]),
+    (Action::DecreaseDetail, &["["]),
+    (Action::IncreaseDetail, &["]"]),
+    (Action::FocusLogs, &["1"]),
+    (Action::FocusDetails, &["2"]),
+    (Action::FocusDebug, &["3"]),
+    (Action::Help, &["?"]),
+    (Action::NextTab, &["tab"]),
+    (Action::PrevTab, &["shift-tab"]),
+    (Action::OpenTab, &["T"]),
+    (Action::CloseTab, &["W"]),
+];
+
+/// One key press with its modifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        // shift is part of the character (`G`) or of the key (`BackTab`)
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        let code = match code {
+            // terminals report ctrl-shift-c as ctrl-C
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => {
+                KeyCode::Char(c.to_ascii_lowercase())
+            }
+            code => code,
+        };
+        Self { code, modifiers }
+    }
+
+    /// a single key with optional `ctrl-`/`alt-`/`shift-` prefixes, e.g. `G`, `ctrl-c`, `pagedown`
+    fn parse(token: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = token;
+        while let Some((prefix, key)) = rest.split_once('-')
+            && !key.is_empty()
+        {
+            modifiers |= match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+            rest = key;
+        }
+
+        let shift = modifiers.contains(KeyModifiers::SHIFT);
+        let mut chars = rest.chars();
+        let code = match (chars.next()?, chars.next()) {
+            (c, None) if shift => KeyCode::Char(c.to_ascii_uppercase()),
+            (c, None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "tab" if shift => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "backspace" => KeyCode::Backspace,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "insert" => KeyCode::Insert,
+                "delete" => KeyCode::Delete,
+                name => match name.strip_prefix('f').map(str::parse::<u8>) {
+                    Some(Ok(n @ 1..=12)) => KeyCode::F(n),
+                    _ => return None,
+                },
+            },
+        };
+        Some(Self::new(code, modifiers))
+    }
+
+    fn is_plain_char(&self) -> bool {
+        matches!(self.code, KeyCode::Char(c) if c != ' ') && self.modifiers.is_empty()
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            code => write!(f, "{}", code),
+        }
+    }
+}
+
+/// Keys pressed one after another to trigger an action, e.g. `gg`.
+///
+/// Parsed from whitespace-separated keys: a single character (`G`, `/`), a named key
+/// with optional modifiers (`esc`, `space`, `shift-tab`, `ctrl-d`, `f5`), or several
+/// characters typed in sequence (`gg`, the same as `g g`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeySequence(Vec<KeyChord>);
+
+impl FromStr for KeySequence {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chords = Vec::new();
+        for token in s.split_whitespace() {
+            if let Some(chord) = KeyChord::parse(token) {
+                chords.push(chord);
+            } else if !token.contains('-') {
+                chords.extend(
+                    token
+                        .chars()
+                        .map(|c| KeyChord::new(KeyCode::Char(c), KeyModifiers::NONE)),
+                );
+            } else {
+                return Err(anyhow!("unknown key `{}`", token));
+            }
+        }
+        if chords.is_empty() {
+            bail!("empty key binding");
+        }
+        Ok(Self(chords))
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `gg` reads better than `g g`, named keys need the separator
+        let separator = if self.0.iter().all(KeyChord::is_plain_char) {
+            ""
+        } else {
+            " "
+        };
+        for (i, chord) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", separator)?;
+            }
+            write!(f, "{}", chord)?;
+        }
+        Ok(())
+    }
+}
+
+/// result of looking up the keys pressed so far
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Lookup {
+    Action(Action),
+    /// the keys start a longer binding, wait for the next one
+    Prefix,
+    Unbound,
+}
+
+/// Which keys trigger which [`Action`], set with [`AppDesc::keymap`](crate::AppDesc::keymap).
+///
+/// # Examples
+///
+/// ```rust
+/// use lazylog_framework::{Action, Keymap};
+///
+/// // Colemak: move with n/e instead of j/k
+/// let mut keymap = Keymap::default();
+/// keymap.bind(Action::NextLog, ["n".parse()?, "down".parse()?]);
+/// keymap.bind(Action::PrevLog, ["e".parse()?, "up".parse()?]);
+/// keymap.bind(Action::NextMatch, ["k".parse()?]);
+/// keymap.bind(Action::Export, ["ctrl-e".parse()?]);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: Vec<(KeySequence, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = DEFAULT_BINDINGS
+            .iter()
+            .flat_map(|(action, keys)| {
+                keys.iter().map(move |keys| {
+                    let keys = keys.parse().expect("default bindings are valid");
+                    (keys, *action)
+                })
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Replaces the keys of `action`, the keys are taken from any action they were bound to.
+    ///
+    /// No keys leave the action unbound. A key bound on its own shadows longer
+    /// bindings starting with it.
+    pub fn bind(&mut self, action: Action, keys: impl IntoIterator<Item = KeySequence>) {
+        let keys: Vec<KeySequence> = keys.into_iter().collect();
+        self.bindings
+            .retain(|(bound, bound_action)| *bound_action != action && !keys.contains(bound));
+        self.bindings
+            .extend(keys.into_iter().map(|keys| (keys, action)));
+    }
+
+    /// The keys bound to `action`.
+    pub fn keys(&self, action: Action) -> impl Iterator<Item = &KeySequence> {
+        self.bindings
+            .iter()
+            .filter(move |(_, bound)| *bound == action)
+            .map(|(keys, _)| keys)
+    }
+
+    /// keys of `action` for display, e.g. `j/↓`
+    pub(crate) fn describe(&self, action: Action) -> String {
+        self.keys(action)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// ` (<keys> to <what>)` for a status message, empty while `action` is unbound
+    pub(crate) fn hint(&self, action: Action, what: &str) -> String {
+        match self.describe(action) {
+            keys if keys.is_empty() => String::new(),
+            keys => format!(" ({} to {})", keys, what),
+        }
+    }
+
+    pub(crate) fn lookup(&self, pressed: &[KeyChord]) -> Lookup {
+        let mut lookup = Lookup::Unbound;
+        for (keys, action) in &self.bindings {
+            if keys.0 == pressed {
+                return Lookup::Action(*action);
+            }
+            if keys.0.starts_with(pressed) {
+                lookup = Lookup::Prefix;
+            }
+        }
+        lookup
+    }
+}
+
+/// keys of a help line
+pub(crate) enum HelpKeys {
+    /// the keys bound to these actions in the active keymap
+    Bound(&'static [Action]),
+    /// keys that are not remappable
+    Fixed(&'static str),
+}
+
+pub(crate) struct HelpSection {
+    pub title: &'static str,
+    /// only shown when the app runs in tabs
+    pub tabs_only: bool,
+    pub entries: &'static [(HelpKeys, &'static str)],
+}
+
+/// layout of the help popup, its keys come from the keymap
+pub(crate) const HELP_SECTIONS: &[HelpSection] = &[
+    HelpSection {
+        title: "Navigation:",
+        tabs_only: false,
+        entries: &[
+            (
+                HelpKeys::Bound(&[Action::NextLog, Action::PrevLog]),
+                "Move to next/prev log",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ScrollLeft, Action::ScrollRight]),
+                "Scroll left/right",
+            ),
+            (
+                HelpKeys::Bound(&[Action::JumpToTop]),
+                "Jump to top (oldest log)",
+            ),
+            (
+                HelpKeys::Bound(&[Action::JumpToBottom]),
+                "Jump to bottom (latest log)",
+            ),
+            (
+                HelpKeys::Bound(&[Action::RevealSelection]),
+                "Make selected log visible in view",
+            ),
+        ],
+    },
+    HelpSection {
+        title: "Actions:",
+        tabs_only: false,
+        entries: &[
+            (HelpKeys::Bound(&[Action::Filter]), "Enter filter mode"),
+            (
+                HelpKeys::Fixed("Tab"),
+                "Cycle literal/case/regex (while filtering)",
+            ),
+            (
+                HelpKeys::Bound(&[Action::Search]),
+                "Search without hiding other logs",
+            ),
+            (
+                HelpKeys::Bound(&[Action::NextMatch, Action::PrevMatch]),
+                "Jump to next/prev search match",
+            ),
+            (HelpKeys::Bound(&[Action::VisualMode]), "Enter visual mode"),
+            (
+                HelpKeys::Bound(&[Action::Yank]),
+                "Copy selected log(s) to clipboard",
+            ),
+            (
+                HelpKeys::Bound(&[Action::YankAll]),
+                "Copy all displayed logs to clipboard",
+            ),
+            (
+                HelpKeys::Bound(&[Action::Export]),
+                "Export displayed/selected logs to a file",
+            ),
+            (HelpKeys::Bound(&[Action::Clear]), "Clear all logs"),
+            (
+                HelpKeys::Bound(&[Action::ToggleWrap]),
+                "Toggle text wrapping",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleFolds]),
+                "Collapse/expand JSON in details",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleMouseCapture]),
+                "Toggle mouse capture (select text when off)",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleDebugLogs]),
+                "Toggle debug logs panel",
+            ),
+            (
+                HelpKeys::Bound(&[Action::CycleMinLevel]),
+                "Cycle minimum level",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleLevel]),
+                "+level: hide/show one level (v d i n w e f)",
+            ),
+            (
+                HelpKeys::Bound(&[Action::CycleContext]),
+                "Cycle context lines around filter matches",
+            ),
+            (
+                HelpKeys::Bound(&[Action::ToggleBookmark]),
+                "Bookmark selected log(s)",
+            ),
+            (
+                HelpKeys::Bound(&[Action::BookmarksOnly]),
+                "Show only bookmarks",
+            ),
+            (
+                HelpKeys::Bound(&[Action::NextBookmark, Action::PrevBookmark]),
+                "Jump to next/prev bookmark",
+            ),
+            (
+                HelpKeys::Bound(&[Action::DecreaseDetail]),
+                "Decrease detail level",
+            ),
+            (
+                HelpKeys::Bound(&[Action::IncreaseDetail]),
+                "Increase detail level",
+            ),
+            (
+                HelpKeys::Bound(&[Action::Back]),
+                "Exit visual / go back / clear search or filter",
+            ),
+            (HelpKeys::Bound(&[Action::Help]), "Toggle this help"),
+            (HelpKeys::Bound(&[Action::Quit]), "Quit program"),
+        ],
+    },
+    HelpSection {
+        title: "Focus:",
+        tabs_only: false,
+        entries: &[
+            (
+                HelpKeys::Bound(&[Action::FocusLogs, Action::FocusDetails, Action::FocusDebug]),
+                "Toggle focus on logs/details/debug panel",
+            ),
+            (
+                HelpKeys::Fixed("Shift+scroll"),
+                "Horizontal scroll with mouse",
+            ),
+        ],
+    },
+    HelpSection {
+        title: "Tabs:",
+        tabs_only: true,
+        entries: &[
+            (
+                HelpKeys::Bound(&[Action::NextTab, Action::PrevTab]),
+                "Switch to next/prev tab",
+            ),
+            (HelpKeys::Bound(&[Action::OpenTab]), "Open a new tab"),
+            (HelpKeys::Bound(&[Action::CloseTab]), "Close current tab"),
+        ],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(s: &str) -> KeySequence {
+        s.parse().unwrap()
+    }
+
+    fn press(keymap: &Keymap, pressed: &str) -> Lookup {
+        keymap.lookup(&keys(pressed).0)
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(keys("gg").to_string(), "gg");
+        assert_eq!(keys("G").to_string(), "G");
+        assert_eq!(keys("ctrl-C").to_string(), "Ctrl+c");
+        assert_eq!(keys("shift-tab").to_string(), "Shift+Tab");
+        assert_eq!(keys("space").to_string(), "space");
+        assert_eq!(keys("-").to_string(), "-");
+        assert_eq!(keys("g down").to_string(), "g ↓");
+        assert_eq!(keys("F5").to_string(), "F5");
+        assert!("ctrl-nope".parse::<KeySequence>().is_err());
+        assert!("ctrl-".parse::<KeySequence>().is_err());
+        // not a function key, typed as f 1 3
+        assert_eq!(keys("f13").to_string(), "f13");
+        assert!("".parse::<KeySequence>().is_err());
+
+        // events carry shift in the character
+        let event = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(KeySequence(vec![event.into()]), keys("G"));
+    }
+
+    #[test]
+    fn test_sequences_and_rebinding() {
+        let mut keymap = Keymap::default();
+        assert_eq!(press(&keymap, "g"), Lookup::Prefix);
+        assert_eq!(press(&keymap, "gg"), Lookup::Action(Action::JumpToTop));
+        assert_eq!(press(&keymap, "Z"), Lookup::Unbound);
+        assert_eq!(press(&keymap, "z"), Lookup::Action(Action::ToggleFolds));
+        assert_eq!(press(&keymap, "ctrl-c"), Lookup::Action(Action::Quit));
+
+        // `n` moves from next-match to next-log
+        keymap.bind(Action::NextLog, [keys("n"), keys("down")]);
+        assert_eq!(press(&keymap, "n"), Lookup::Action(Action::NextLog));
+        assert_eq!(press(&keymap, "j"), Lookup::Unbound);
+        assert_eq!(keymap.describe(Action::NextLog), "n/↓");
+        assert_eq!(keymap.describe(Action::NextMatch), "");
+        assert_eq!(keymap.hint(Action::NextLog, "move"), " (n/↓ to move)");
+        assert_eq!(keymap.hint(Action::NextMatch, "search"), "");
+        assert_eq!(Action::from_name("next-match"), Some(Action::NextMatch));
+    }
+}
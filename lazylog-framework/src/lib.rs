@@ -134,7 +134,9 @@
 //! let parser = Arc::new(MyParser);
 //! let mut desc = AppDesc::new(parser.clone());
 //! desc.poll_interval = Duration::from_millis(50);  // poll every 50ms
-//! desc.ring_buffer_size = 32768;  // 32K log capacity
+//! desc.ring_buffer_size = 32768;  // 32K logs in flight between provider and UI
+//! desc.max_log_items = Some(200_000);          // keep the newest 200K logs
+//! desc.max_log_bytes = Some(256 * 1024 * 1024); // and at most ~256MB
 //! desc.show_debug_logs = true;    // show debug panel
 //!
 //! let provider = MyProvider;
@@ -154,7 +156,13 @@
 //!
 //! ### Filtering
 //! - `/` or `f`: enter filter mode
-//! - Type regex pattern to filter logs in real-time
+//! - Type a pattern to filter logs in real-time
+//! - Terms are ANDed; `AND`/`OR`/`NOT`, parentheses, `"quoted phrases"` and `-negation` are supported
+//! - `key:value` qualifiers (e.g. `level:error tag:Effect`) match against log item metadata
+//! - `level:` compares normalized [`Severity`] levels, so `level:error` also matches logcat `E`
+//!   and iOS `<Error>` items
+//! - `Tab`: cycle between literal (case-insensitive), case-sensitive and regex matching
+//! - Invalid regex patterns are reported in the footer; the last valid filter stays applied
 //! - `Esc`: clear filter
 //! - Filter mode cannot be entered while visual mode is active
 //!
@@ -190,7 +198,7 @@
 //! - **Database logs**: Query and stream from databases
 //! - **API logs**: Fetch from logging services (e.g., Elasticsearch)
 //! - **Device logs**: Monitor mobile devices (iOS, Android)
-//! - **Multi-source aggregation**: Combine multiple log sources
+//! - **Multi-source aggregation**: Combine multiple log sources with [`MultiProvider`]
 //!
 //! ## Examples
 //!
@@ -203,8 +211,10 @@ pub mod provider;
 
 // re-export commonly used types
 pub use provider::{
-    LogDetailLevel, LogItem, LogParser, LogProvider, decrement_detail_level,
-    increment_detail_level, spawn_provider_thread,
+    FileTailProvider, LogDetailLevel, LogItem, LogParser, LogProvider, MultiProvider,
+    OverflowPolicy, OverflowState, PlainTextParser, RawLog, RecordSplit, RecordingProvider,
+    ReplayProvider, ReplaySpeed, Severity, StdinProvider, decrement_detail_level,
+    increment_detail_level, spawn_provider_thread, spawn_provider_thread_with_policy,
 };
 
 // internal modules (not part of public API but needed for app)
@@ -212,11 +222,17 @@ pub(crate) mod app;
 pub(crate) mod app_block;
 pub(crate) mod content_line_maker;
 pub(crate) mod filter;
+pub(crate) mod json_view;
+pub(crate) mod keymap;
 pub(crate) mod log_list;
 pub(crate) mod log_parser;
+pub(crate) mod search;
 pub mod status_bar;
 pub(crate) mod theme;
 pub(crate) mod ui_logger;
 
 // public API for running the application
-pub use app::{AppDesc, start_with_desc, start_with_provider};
+pub use app::{AppDesc, TabDesc, start_with_desc, start_with_provider, start_with_tabs};
+pub use filter::{ContextLine, ContextStream, FilterContext, FilterMode, FilterQuery};
+pub use keymap::{Action, KeySequence, Keymap};
+pub use theme::Theme;
@@ -21,6 +21,22 @@ impl LogList {
         self.indices.get(idx).copied()
     }
 
+    /// Drops entries pointing at the first `count` raw logs and shifts the rest.
+    ///
+    /// The selection follows its item (and is cleared if the item was dropped).
+    /// Returns how many displayed rows were removed.
+    pub fn evict_front(&mut self, count: usize) -> usize {
+        let removed = self.indices.partition_point(|&idx| idx < count);
+        self.indices.drain(..removed);
+        for idx in &mut self.indices {
+            *idx -= count;
+        }
+
+        let selected = self.state.selected().and_then(|i| i.checked_sub(removed));
+        self.state.select(selected);
+        removed
+    }
+
     pub fn select_next_circular(&mut self) {
         let len = self.indices.len();
         if len == 0 {
@@ -114,3 +130,21 @@ impl LogList {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evict_front_keeps_selection_on_same_item() {
+        let mut list = LogList::new(vec![1, 3, 4, 6]);
+        list.state.select(Some(2));
+
+        assert_eq!(list.evict_front(4), 2);
+        assert_eq!(list.indices, vec![0, 2]);
+        assert_eq!(list.state.selected(), Some(0));
+
+        assert_eq!(list.evict_front(1), 1);
+        assert_eq!(list.state.selected(), None);
+    }
+}
@@ -0,0 +1,445 @@
+use super::{LogProvider, RawLog};
+use anyhow::{Context, Result, bail};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// first line of every capture file
+const CAPTURE_HEADER: &str = "lazylog-capture v2";
+
+/// header of captures written before metadata was recorded, still readable
+const CAPTURE_HEADER_V1: &str = "lazylog-capture v1";
+
+/// Records every raw log a provider emits to a capture file, with its arrival time.
+///
+/// The wrapped provider behaves exactly as before; the capture can be played back
+/// later with [`ReplayProvider`] through the same parser.
+///
+/// The file starts with a `lazylog-capture v2` header line, followed by one line per
+/// raw log: milliseconds since start, a tab, the raw log, then a tab and `key=value`
+/// for each [`RawLog`] metadata entry. `\`, newlines, carriage returns and tabs are
+/// escaped, and so is `=` in keys.
+///
+/// A failing write (e.g. a full disk) is logged and ends the recording, the logs
+/// keep flowing to the UI.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lazylog_framework::provider::{FileTailProvider, RecordingProvider};
+///
+/// let provider = FileTailProvider::new("/var/log/app.log");
+/// let provider = RecordingProvider::create(provider, "session.capture").unwrap();
+/// ```
+pub struct RecordingProvider<P> {
+    inner: P,
+    // None once a write failed
+    writer: Option<BufWriter<File>>,
+    started: Instant,
+}
+
+impl<P: LogProvider> RecordingProvider<P> {
+    /// Creates (or truncates) the capture file and wraps `inner`.
+    pub fn create(inner: P, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .with_context(|| format!("cannot create capture file {}", path.display()))?;
+
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "{}", CAPTURE_HEADER)?;
+        writer.flush()?;
+
+        Ok(Self {
+            inner,
+            writer: Some(writer),
+            started: Instant::now(),
+        })
+    }
+
+    /// write a batch to the capture, a failed write stops the recording
+    fn record<'a>(
+        &mut self,
+        mut raw_logs: impl ExactSizeIterator<Item = (&'a str, &'a [(String, String)])>,
+    ) {
+        let Some(writer) = &mut self.writer else {
+            return;
+        };
+        if raw_logs.len() == 0 {
+            return;
+        }
+
+        let elapsed = self.started.elapsed().as_millis();
+        let written = raw_logs
+            .try_for_each(|(text, metadata)| {
+                write!(writer, "{}\t{}", elapsed, escape(text))?;
+                for (key, value) in metadata {
+                    write!(
+                        writer,
+                        "\t{}={}",
+                        escape(key).replace('=', "\\="),
+                        escape(value)
+                    )?;
+                }
+                writeln!(writer)
+            })
+            // flush every batch so the capture survives a crash
+            .and_then(|()| writer.flush());
+
+        if let Err(e) = written {
+            log::error!("Recording stopped, cannot write capture file: {}", e);
+            self.writer = None;
+        }
+    }
+}
+
+impl<P: LogProvider> LogProvider for RecordingProvider<P> {
+    fn start(&mut self) -> Result<()> {
+        self.started = Instant::now();
+        self.inner.start()
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        let flushed = self.writer.as_mut().map_or(Ok(()), |writer| writer.flush());
+        self.inner.stop()?;
+        Ok(flushed?)
+    }
+
+    fn poll_logs(&mut self) -> Result<Vec<String>> {
+        let raw_logs = self.inner.poll_logs()?;
+        self.record(raw_logs.iter().map(|text| (text.as_str(), &[][..])));
+        Ok(raw_logs)
+    }
+
+    fn poll_raw_logs(&mut self) -> Result<Vec<RawLog>> {
+        let raw_logs = self.inner.poll_raw_logs()?;
+        self.record(
+            raw_logs
+                .iter()
+                .map(|raw_log| (raw_log.text.as_str(), raw_log.metadata.as_slice())),
+        );
+        Ok(raw_logs)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+}
+
+/// How fast a [`ReplayProvider`] plays a capture back.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ReplaySpeed {
+    /// everything at once
+    #[default]
+    Instant,
+    /// original timing divided by the factor (`1.0` is real time, `2.0` twice as fast)
+    Scaled(f64),
+}
+
+/// Plays back a capture file written by [`RecordingProvider`].
+///
+/// Raw logs are returned as recorded, with their metadata, so the same
+/// [`LogParser`](crate::LogParser) produces the same items. Captures in the older
+/// `lazylog-capture v1` format, without metadata, are read as well. The provider
+/// [finishes](LogProvider::is_finished) once every log was returned.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lazylog_framework::provider::{ReplayProvider, ReplaySpeed};
+///
+/// let provider = ReplayProvider::open("session.capture")
+///     .unwrap()
+///     .speed(ReplaySpeed::Scaled(4.0));
+/// ```
+pub struct ReplayProvider {
+    records: Vec<(Duration, RawLog)>,
+    next: usize,
+    speed: ReplaySpeed,
+    started: Instant,
+}
+
+impl ReplayProvider {
+    /// Reads the whole capture file, failing on a missing header or malformed lines.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|| format!("cannot open capture file {}", path.display()))?;
+
+        let mut lines = BufReader::new(file).lines();
+        let with_metadata = match lines.next().transpose()? {
+            Some(header) if header.trim_end() == CAPTURE_HEADER => true,
+            Some(header) if header.trim_end() == CAPTURE_HEADER_V1 => false,
+            _ => bail!("{} is not a lazylog capture file", path.display()),
+        };
+
+        let mut records = Vec::new();
+        for (line_no, line) in lines.enumerate() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let record = parse_record(&line, with_metadata)
+                .with_context(|| format!("malformed capture line {}", line_no + 2))?;
+            records.push(record);
+        }
+
+        Ok(Self {
+            records,
+            next: 0,
+            speed: ReplaySpeed::default(),
+            started: Instant::now(),
+        })
+    }
+
+    /// Playback speed (default: [`ReplaySpeed::Instant`]).
+    pub fn speed(mut self, speed: ReplaySpeed) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// capture time that has been reached at the current playback position
+    fn playback_position(&self) -> Option<Duration> {
+        match self.speed {
+            ReplaySpeed::Instant => None,
+            ReplaySpeed::Scaled(factor) => {
+                Some(self.started.elapsed().mul_f64(factor.max(f64::EPSILON)))
+            }
+        }
+    }
+}
+
+impl LogProvider for ReplayProvider {
+    fn start(&mut self) -> Result<()> {
+        self.next = 0;
+        self.started = Instant::now();
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn poll_logs(&mut self) -> Result<Vec<String>> {
+        Ok(self
+            .poll_raw_logs()?
+            .into_iter()
+            .map(|raw_log| raw_log.text)
+            .collect())
+    }
+
+    fn poll_raw_logs(&mut self) -> Result<Vec<RawLog>> {
+        let end = match self.playback_position() {
+            None => self.records.len(),
+            Some(position) => {
+                self.next
+                    + self.records[self.next..].partition_point(|(offset, _)| *offset <= position)
+            }
+        };
+
+        let logs = self.records[self.next..end]
+            .iter()
+            .map(|(_, raw_log)| raw_log.clone())
+            .collect();
+        self.next = end;
+        Ok(logs)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.next >= self.records.len()
+    }
+}
+
+fn parse_record(line: &str, with_metadata: bool) -> Result<(Duration, RawLog)> {
+    let (millis, rest) = line.split_once('\t').context("missing tab separator")?;
+    let millis: u64 = millis.parse().context("invalid timestamp")?;
+    let offset = Duration::from_millis(millis);
+
+    // v1 did not escape tabs, everything after the first one is the raw log
+    if !with_metadata {
+        return Ok((offset, RawLog::new(unescape(rest))));
+    }
+
+    let mut fields = rest.split('\t');
+    let mut raw_log = RawLog::new(unescape(fields.next().unwrap_or_default()));
+    for field in fields {
+        let split = unescaped_equals(field).context("metadata without `=`")?;
+        raw_log = raw_log.with_metadata(unescape(&field[..split]), unescape(&field[split + 1..]));
+    }
+    Ok((offset, raw_log))
+}
+
+/// byte offset of the first `=` that is not escaped
+fn unescaped_equals(field: &str) -> Option<usize> {
+    let mut chars = field.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '=' => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn escape(raw_log: &str) -> String {
+    let mut escaped = String::with_capacity(raw_log.len());
+    for c in raw_log.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(escaped: &str) -> String {
+    let mut raw_log = String::with_capacity(escaped.len());
+    let mut chars = escaped.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            raw_log.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => raw_log.push('\n'),
+            Some('r') => raw_log.push('\r'),
+            Some('t') => raw_log.push('\t'),
+            Some(other) => raw_log.push(other),
+            None => raw_log.push('\\'),
+        }
+    }
+    raw_log
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::test_support::{OnceProvider, temp_path};
+    use std::fs;
+
+    #[test]
+    fn test_record_then_replay_round_trip() {
+        let path = temp_path("roundtrip", "capture");
+        let logs = vec![
+            "plain".to_string(),
+            "multi\nline\twith tab".to_string(),
+            r"back\slash \n literal".to_string(),
+        ];
+
+        let mut recorder =
+            RecordingProvider::create(OnceProvider::new(logs.clone()), &path).unwrap();
+        recorder.start().unwrap();
+        assert_eq!(recorder.poll_logs().unwrap(), logs);
+        recorder.stop().unwrap();
+
+        let mut replay = ReplayProvider::open(&path).unwrap();
+        replay.start().unwrap();
+        assert!(!replay.is_finished());
+        assert_eq!(replay.poll_logs().unwrap(), logs);
+        assert!(replay.is_finished());
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_metadata_survives_replay() {
+        let path = temp_path("metadata", "capture");
+        let logs = vec![
+            RawLog::new("tab\tin text").with_metadata("file", "a=b.log"),
+            RawLog::new("odd keys")
+                .with_metadata("k=ey", "back\\slash\tand tab")
+                .with_metadata("empty", ""),
+            RawLog::new("none"),
+        ];
+
+        let mut recorder =
+            RecordingProvider::create(OnceProvider::new(logs.clone()), &path).unwrap();
+        recorder.start().unwrap();
+        assert_eq!(recorder.poll_raw_logs().unwrap(), logs);
+        recorder.stop().unwrap();
+
+        let mut replay = ReplayProvider::open(&path).unwrap();
+        replay.start().unwrap();
+        assert_eq!(replay.poll_raw_logs().unwrap(), logs);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_v1_capture_is_still_readable() {
+        let path = temp_path("v1", "capture");
+        fs::write(&path, format!("{}\n0\tcolumn\tsplit\n", CAPTURE_HEADER_V1)).unwrap();
+
+        let mut replay = ReplayProvider::open(&path).unwrap();
+        replay.start().unwrap();
+        assert_eq!(
+            replay.poll_raw_logs().unwrap(),
+            vec![RawLog::new("column\tsplit")]
+        );
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_write_error_stops_recording_but_keeps_logs() {
+        let path = temp_path("full", "capture");
+        let logs = vec!["first".to_string()];
+        let mut recorder =
+            RecordingProvider::create(OnceProvider::new(logs.clone()), &path).unwrap();
+        // every write to /dev/full fails with "no space left on device"
+        let full = File::options().write(true).open("/dev/full").unwrap();
+        recorder.writer = Some(BufWriter::new(full));
+
+        recorder.start().unwrap();
+        assert_eq!(recorder.poll_logs().unwrap(), logs);
+        assert!(recorder.writer.is_none());
+        recorder.stop().unwrap();
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_scaled_replay_follows_capture_timing() {
+        let path = temp_path("scaled", "capture");
+        fs::write(
+            &path,
+            format!("{}\n0\tfirst\n600000\tlater\n", CAPTURE_HEADER),
+        )
+        .unwrap();
+
+        let mut replay = ReplayProvider::open(&path)
+            .unwrap()
+            .speed(ReplaySpeed::Scaled(1.0));
+        replay.start().unwrap();
+        assert_eq!(replay.poll_logs().unwrap(), vec!["first"]);
+        assert!(replay.poll_logs().unwrap().is_empty());
+        assert!(!replay.is_finished());
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_open_rejects_other_files() {
+        let path = temp_path("invalid", "capture");
+        fs::write(&path, "just some log\n").unwrap();
+        assert!(ReplayProvider::open(&path).is_err());
+        fs::remove_file(&path).ok();
+    }
+}
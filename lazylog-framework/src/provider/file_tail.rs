@@ -0,0 +1,304 @@
+use super::{
+    LogProvider,
+    records::{RecordBuffer, RecordSplit},
+};
+use anyhow::Result;
+use std::{
+    fs::{self, File},
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+/// upper bound for a single poll so a huge existing file doesn't stall the provider thread
+const MAX_READ_PER_POLL: u64 = 4 * 1024 * 1024;
+
+/// Follows a file like `tail -F`.
+///
+/// - Only new content is returned unless [`FileTailProvider::read_existing`] is set
+/// - Truncation (e.g. `> app.log` or copytruncate) restarts reading from the beginning
+/// - Rename-rotation (`app.log` -> `app.log.1`, new `app.log`) drains the old file and
+///   then follows the new one from its start (detected by inode on unix)
+/// - A missing file is waited for
+///
+/// Lines are grouped into records with a [`RecordSplit`], one record per line by default.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lazylog_framework::provider::{FileTailProvider, RecordSplit};
+///
+/// let provider = FileTailProvider::new("/var/log/app.log")
+///     .read_existing(true)
+///     .record_split(RecordSplit::start_pattern(r"^\d{4}-\d{2}-\d{2} ").unwrap());
+/// ```
+pub struct FileTailProvider {
+    path: PathBuf,
+    read_existing: bool,
+    buffer: RecordBuffer,
+    file: Option<File>,
+    file_id: Option<FileId>,
+    offset: u64,
+}
+
+/// identity of the file behind a path, used to detect rename-rotation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FileId {
+    dev: u64,
+    ino: u64,
+}
+
+impl FileTailProvider {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            read_existing: false,
+            buffer: RecordBuffer::new(RecordSplit::Lines),
+            file: None,
+            file_id: None,
+            offset: 0,
+        }
+    }
+
+    /// Read the content already in the file before following it (default: false).
+    pub fn read_existing(mut self, read_existing: bool) -> Self {
+        self.read_existing = read_existing;
+        self
+    }
+
+    /// How lines are grouped into records (default: [`RecordSplit::Lines`]).
+    pub fn record_split(mut self, split: RecordSplit) -> Self {
+        self.buffer = RecordBuffer::new(split);
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn open(&mut self, from_start: bool) -> io::Result<()> {
+        let file = File::open(&self.path)?;
+        let metadata = file.metadata()?;
+
+        self.offset = if from_start { 0 } else { metadata.len() };
+        self.file_id = file_id(&metadata);
+        self.file = Some(file);
+        Ok(())
+    }
+
+    /// open the file if it exists, returns false while waiting for it
+    fn try_open(&mut self, from_start: bool) -> Result<bool> {
+        match self.open(from_start) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// read new bytes from the open file, returns whether anything was read
+    fn read_available(&mut self, records: &mut Vec<String>) -> Result<bool> {
+        let len = match &self.file {
+            Some(file) => file.metadata()?.len(),
+            None => return Ok(false),
+        };
+        if len < self.offset {
+            // truncated in place, emit what was left of the old content and start over
+            log::debug!("{} was truncated, reading from start", self.path.display());
+            records.extend(self.buffer.flush());
+            self.offset = 0;
+        }
+        if len == self.offset {
+            return Ok(false);
+        }
+
+        let Some(file) = &mut self.file else {
+            return Ok(false);
+        };
+
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buf = Vec::new();
+        let read = file.take(MAX_READ_PER_POLL).read_to_end(&mut buf)?;
+        self.offset += read as u64;
+
+        records.extend(self.buffer.push(&buf));
+        Ok(read > 0)
+    }
+
+    fn is_rotated(&self) -> bool {
+        let Some(current) = self.file_id else {
+            return false;
+        };
+
+        match fs::metadata(&self.path) {
+            Ok(metadata) => file_id(&metadata).is_some_and(|id| id != current),
+            // renamed away without a replacement (yet)
+            Err(_) => true,
+        }
+    }
+}
+
+impl LogProvider for FileTailProvider {
+    fn start(&mut self) -> Result<()> {
+        if !self.try_open(self.read_existing)? {
+            log::debug!("Waiting for {} to be created", self.path.display());
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.file = None;
+        self.file_id = None;
+        Ok(())
+    }
+
+    fn poll_logs(&mut self) -> Result<Vec<String>> {
+        let mut records = Vec::new();
+
+        // a file that appears later (or after rotation) is new, read it from the start
+        if self.file.is_none() && !self.try_open(true)? {
+            return Ok(records);
+        }
+
+        let mut read_any = self.read_available(&mut records)?;
+
+        if self.is_rotated() {
+            log::debug!("{} was rotated, following new file", self.path.display());
+
+            // drain whatever was written to the old file before the rename
+            while self.read_available(&mut records)? {}
+            records.extend(self.buffer.flush());
+
+            self.file = None;
+            self.file_id = None;
+            if self.try_open(true)? {
+                read_any |= self.read_available(&mut records)?;
+            }
+        }
+
+        if !read_any {
+            records.extend(self.buffer.flush_if_quiet());
+        }
+
+        Ok(records)
+    }
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> Option<FileId> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(FileId {
+        dev: metadata.dev(),
+        ino: metadata.ino(),
+    })
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &fs::Metadata) -> Option<FileId> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::test_support::temp_path;
+    use std::io::Write;
+
+    fn append(path: &Path, text: &str) {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn test_reads_existing_and_appended_lines() {
+        let path = temp_path("existing", "log");
+        append(&path, "one\ntwo\n");
+
+        let mut provider = FileTailProvider::new(&path).read_existing(true);
+        provider.start().unwrap();
+        assert_eq!(provider.poll_logs().unwrap(), vec!["one", "two"]);
+
+        append(&path, "thr");
+        assert!(provider.poll_logs().unwrap().is_empty());
+        append(&path, "ee\n");
+        assert_eq!(provider.poll_logs().unwrap(), vec!["three"]);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_skips_existing_content_by_default() {
+        let path = temp_path("skip", "log");
+        append(&path, "old\n");
+
+        let mut provider = FileTailProvider::new(&path);
+        provider.start().unwrap();
+        append(&path, "new\n");
+        assert_eq!(provider.poll_logs().unwrap(), vec!["new"]);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_truncation_restarts_from_beginning() {
+        let path = temp_path("truncate", "log");
+        append(&path, "a long first line\n");
+
+        let mut provider = FileTailProvider::new(&path).read_existing(true);
+        provider.start().unwrap();
+        provider.poll_logs().unwrap();
+
+        fs::write(&path, "short\n").unwrap();
+        assert_eq!(provider.poll_logs().unwrap(), vec!["short"]);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_truncation_emits_pending_record() {
+        let path = temp_path("truncate-pending", "log");
+        append(&path, "# first\ndetail\n# second\nunterminated");
+
+        let mut provider = FileTailProvider::new(&path)
+            .read_existing(true)
+            .record_split(RecordSplit::start_pattern("^# ").unwrap());
+        provider.start().unwrap();
+        assert_eq!(provider.poll_logs().unwrap(), vec!["# first\ndetail"]);
+
+        // the new content doesn't start a record, it must not join the old one
+        fs::write(&path, "new\n").unwrap();
+        assert_eq!(
+            provider.poll_logs().unwrap(),
+            vec!["# second\nunterminated"]
+        );
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rename_rotation_drains_old_file() {
+        let path = temp_path("rotate", "log");
+        let rotated = path.with_extension("log.1");
+        append(&path, "first\n");
+
+        let mut provider = FileTailProvider::new(&path).read_existing(true);
+        provider.start().unwrap();
+        assert_eq!(provider.poll_logs().unwrap(), vec!["first"]);
+
+        append(&path, "last before rotation\n");
+        fs::rename(&path, &rotated).unwrap();
+        append(&path, "after rotation\n");
+
+        assert_eq!(
+            provider.poll_logs().unwrap(),
+            vec!["last before rotation", "after rotation"]
+        );
+
+        fs::remove_file(&path).ok();
+        fs::remove_file(&rotated).ok();
+    }
+}
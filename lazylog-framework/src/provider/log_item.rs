@@ -1,14 +1,21 @@
-use chrono::Local;
+use super::raw_log::RawLog;
+use super::severity::Severity;
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, TimeDelta, TimeZone};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// format of [`LogItem::time`]
+const TIME_FORMAT: &str = "%H:%M:%S%.3f";
+
 /// A structured representation of a single log entry.
 ///
 /// `LogItem` is the core data structure passed from providers to the UI. It contains:
 /// - `id`: Unique identifier for deduplication and selection tracking
-/// - `time`: Human-readable timestamp (formatted by parser or auto-generated)
+/// - `time`: Human-readable timestamp (derived from `timestamp` or auto-generated)
+/// - `timestamp`: Typed timestamp parsed from the source text, if it has one
 /// - `content`: Parsed/formatted log message
 /// - `raw_content`: Original unparsed log line
+/// - `severity`: Normalized level, shared by coloring and level filters
 /// - `metadata`: Extensible key-value storage for custom fields
 ///
 /// # Metadata Pattern
@@ -38,12 +45,19 @@ pub struct LogItem {
     /// human-readable timestamp (e.g., "14:30:25.123")
     pub time: String,
 
+    /// timestamp parsed from the log source, `None` when the source has none
+    /// (in which case `time` holds the ingestion time)
+    pub timestamp: Option<DateTime<Local>>,
+
     /// parsed/formatted log content
     pub content: String,
 
     /// original raw log line
     pub raw_content: String,
 
+    /// normalized level, `None` when the source has none or it is not recognized
+    pub severity: Option<Severity>,
+
     /// extensible metadata (level, module, tags, etc.)
     pub metadata: HashMap<String, String>,
 }
@@ -51,6 +65,9 @@ pub struct LogItem {
 impl LogItem {
     /// Creates a new log item with auto-generated ID and timestamp.
     ///
+    /// `time` is set to the ingestion time; parsers that can read the real
+    /// timestamp from the source should call [`LogItem::with_timestamp`].
+    ///
     /// # Parameters
     ///
     /// - `content`: The parsed log message (can be same as `raw_content` for simple logs)
@@ -77,13 +94,66 @@ impl LogItem {
     pub fn new(content: String, raw_content: String) -> Self {
         Self {
             id: Uuid::new_v4(),
-            time: Local::now().format("%H:%M:%S%.3f").to_string(),
+            time: Local::now().format(TIME_FORMAT).to_string(),
+            timestamp: None,
             content,
             raw_content,
+            severity: None,
             metadata: HashMap::new(),
         }
     }
 
+    /// Sets the source timestamp and derives `time` from it (builder pattern).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{Local, TimeZone};
+    /// use lazylog_framework::LogItem;
+    ///
+    /// let timestamp = Local.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap();
+    /// let log = LogItem::new("msg".into(), "raw".into()).with_timestamp(timestamp);
+    ///
+    /// assert_eq!(log.time, "10:30:00.000");
+    /// assert_eq!(log.timestamp, Some(timestamp));
+    /// ```
+    pub fn with_timestamp(mut self, timestamp: DateTime<Local>) -> Self {
+        self.time = timestamp.format(TIME_FORMAT).to_string();
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the normalized severity (builder pattern).
+    ///
+    /// Prefer [`LogItem::with_level`] when the source has a level string.
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = Some(severity);
+        self
+    }
+
+    /// Stores the source level as `level` metadata and its normalized severity (builder pattern).
+    ///
+    /// The original spelling is kept for display; `severity` is `None` when
+    /// [`Severity::from_level`] doesn't recognize it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lazylog_framework::LogItem;
+    /// use lazylog_framework::provider::Severity;
+    ///
+    /// let log = LogItem::new("msg".into(), "raw".into()).with_level("Notice");
+    ///
+    /// assert_eq!(log.get_metadata("level"), Some("Notice"));
+    /// assert_eq!(log.severity, Some(Severity::Notice));
+    /// ```
+    pub fn with_level(mut self, level: impl Into<String>) -> Self {
+        let level = level.into();
+        self.severity = Severity::from_level(&level);
+        self.metadata.insert("level".to_string(), level);
+        self
+    }
+
     /// Adds metadata to the log item (builder pattern).
     ///
     /// Common metadata keys:
@@ -125,6 +195,58 @@ impl LogItem {
     pub fn get_metadata(&self, key: &str) -> Option<&str> {
         self.metadata.get(key).map(|s| s.as_str())
     }
+
+    /// approximate heap + inline size, used for byte-based retention
+    pub(crate) fn approx_size(&self) -> usize {
+        let metadata: usize = self
+            .metadata
+            .iter()
+            .map(|(key, value)| key.len() + value.len() + 2 * std::mem::size_of::<String>())
+            .sum();
+
+        std::mem::size_of::<Self>()
+            + self.time.len()
+            + self.content.len()
+            + self.raw_content.len()
+            + metadata
+    }
+}
+
+/// Resolves a timestamp that carries no year (e.g. `MM-DD HH:MM:SS` in logcat or
+/// `Oct 29 11:27:36` in syslog) against a reference time.
+///
+/// Picks the year closest to `reference`, but never more than a day after it, so a
+/// December log replayed in January resolves to the previous year. Use the current
+/// time as reference for live logs, or a nearby full timestamp when one is available.
+///
+/// Returns `None` for invalid dates (e.g. Feb 30) or local times skipped by DST.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{Datelike, Local, NaiveTime, TimeZone};
+/// use lazylog_framework::provider::resolve_yearless_timestamp;
+///
+/// let reference = Local.with_ymd_and_hms(2026, 1, 2, 12, 0, 0).unwrap();
+/// let time = NaiveTime::from_hms_opt(23, 59, 0).unwrap();
+///
+/// let resolved = resolve_yearless_timestamp(12, 31, time, reference).unwrap();
+/// assert_eq!(resolved.year(), 2025);
+/// ```
+pub fn resolve_yearless_timestamp(
+    month: u32,
+    day: u32,
+    time: NaiveTime,
+    reference: DateTime<Local>,
+) -> Option<DateTime<Local>> {
+    let latest = reference + TimeDelta::days(1);
+
+    [reference.year() - 1, reference.year(), reference.year() + 1]
+        .into_iter()
+        .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+        .filter_map(|date| Local.from_local_datetime(&date.and_time(time)).earliest())
+        .filter(|candidate| *candidate <= latest)
+        .min_by_key(|candidate| (*candidate - reference).abs())
 }
 
 /// Detail level for log display (0-255, parser-defined).
@@ -283,6 +405,20 @@ pub trait LogParser: Send + Sync {
     /// ```
     fn parse(&self, raw_log: &str) -> Option<LogItem>;
 
+    /// Parses a [`RawLog`] and adds its provider metadata to the item.
+    ///
+    /// This is what the framework calls. The default parses the text with
+    /// [`LogParser::parse`]; override it when the parser depends on the provider
+    /// metadata, like [`MultiParser`](super::MultiParser) picking the parser of the
+    /// source a log came from.
+    fn parse_raw(&self, raw_log: &RawLog) -> Option<LogItem> {
+        let mut item = self.parse(&raw_log.text)?;
+        for (key, value) in &raw_log.metadata {
+            item.metadata.insert(key.clone(), value.clone());
+        }
+        Some(item)
+    }
+
     /// Formats a log item for display at a given detail level.
     ///
     /// Called frequently during rendering. Returns the string to display in the log list.
@@ -379,6 +515,36 @@ pub trait LogParser: Send + Sync {
         format!("{} {}", item.time, item.raw_content)
     }
 
+    /// Returns the severity of a log item, used for coloring and level filters.
+    ///
+    /// Default: [`LogItem::severity`], falling back to the `level` metadata mapped
+    /// with [`Severity::from_level`] for items built without [`LogItem::with_level`]
+    ///
+    /// Override if the level is stored elsewhere or uses custom names:
+    ///
+    /// ```rust
+    /// use lazylog_framework::{LogParser, LogItem};
+    /// use lazylog_framework::provider::Severity;
+    /// # struct MyParser;
+    /// impl LogParser for MyParser {
+    ///     # fn parse(&self, _: &str) -> Option<LogItem> { None }
+    ///     # fn format_preview(&self, _: &LogItem, _: u8) -> String { String::new() }
+    ///     # fn get_searchable_text(&self, _: &LogItem, _: u8) -> String { String::new() }
+    ///     fn severity(&self, item: &LogItem) -> Option<Severity> {
+    ///         match item.get_metadata("priority")? {
+    ///             "0" | "1" | "2" => Some(Severity::Fatal),
+    ///             "3" => Some(Severity::Error),
+    ///             "4" => Some(Severity::Warning),
+    ///             _ => Some(Severity::Info),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn severity(&self, item: &LogItem) -> Option<Severity> {
+        item.severity
+            .or_else(|| item.get_metadata("level").and_then(Severity::from_level))
+    }
+
     /// Returns the maximum detail level supported by this parser.
     ///
     /// Default: `4` (5 levels: 0-4)
@@ -6,6 +6,16 @@
 //! - [`LogParser`]: Parses and formats logs for display
 //! - [`LogItem`]: Structured representation of a log entry
 //!
+//! Ready-made building blocks:
+//!
+//! - [`FileTailProvider`]: Follows a file like `tail -F`
+//! - [`StdinProvider`]: Reads piped standard input
+//! - [`PlainTextParser`]: Shows unstructured text as-is
+//! - [`RecordSplit`]: Groups multi-line entries into single records
+//! - [`RecordBuffer`]: Turns bytes read from a growing file into records
+//! - [`RecordingProvider`] / [`ReplayProvider`]: Capture a session to a file and play it back
+//! - [`MultiProvider`]: Merges several provider/parser pairs into one time-ordered stream
+//!
 //! # Architecture
 //!
 //! The provider pattern separates concerns:
@@ -27,23 +37,46 @@
 //! - Same parser with different providers (e.g., file vs network)
 //! - Easy testing of parsing logic independently
 
+mod capture;
+mod file_tail;
 mod log_item;
+mod multi;
+mod plain_text;
+mod raw_log;
+mod records;
+mod severity;
+mod stdin;
+#[cfg(test)]
+pub(crate) mod test_support;
 
+pub use capture::{RecordingProvider, ReplayProvider, ReplaySpeed};
+pub use file_tail::FileTailProvider;
 pub use log_item::{
     LogDetailLevel, LogItem, LogParser, decrement_detail_level, increment_detail_level,
+    resolve_yearless_timestamp,
 };
+pub use multi::{MultiParser, MultiProvider, SOURCE_METADATA_KEY, merge_sources};
+pub use plain_text::PlainTextParser;
+pub use raw_log::RawLog;
+pub use records::{RecordAssembler, RecordBuffer, RecordSplit};
+pub use severity::Severity;
+pub use stdin::StdinProvider;
 
 use anyhow::Result;
-use ringbuf::traits::Producer;
+use ringbuf::traits::{Consumer, Producer};
 use std::{
+    collections::VecDeque,
     sync::{
-        Arc,
-        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
     },
     thread,
     time::Duration,
 };
 
+/// how long a blocked provider thread waits before retrying a full ring buffer
+const BLOCKED_RETRY_INTERVAL: Duration = Duration::from_millis(5);
+
 /// Trait for acquiring raw log data from any source.
 ///
 /// Implement this trait to define where logs come from (files, network, APIs, etc.).
@@ -178,6 +211,120 @@ pub trait LogProvider: Send {
     /// }
     /// ```
     fn poll_logs(&mut self) -> Result<Vec<String>>;
+
+    /// Poll for new logs together with provider metadata (non-blocking).
+    ///
+    /// This is what the framework calls. The default wraps [`LogProvider::poll_logs`]
+    /// without metadata; override it when the provider knows something the text
+    /// doesn't carry, such as which of several followed files a log came from.
+    /// `poll_logs()` should then return the same texts.
+    fn poll_raw_logs(&mut self) -> Result<Vec<RawLog>> {
+        Ok(self.poll_logs()?.into_iter().map(RawLog::from).collect())
+    }
+
+    /// Whether the source is exhausted and will never produce more logs.
+    ///
+    /// Live sources (devices, followed files) never finish, which is the default.
+    /// Finite sources such as piped stdin return `true` once their input is closed
+    /// and every log has been returned from `poll_logs()`, which lets non-interactive
+    /// consumers exit.
+    fn is_finished(&self) -> bool {
+        false
+    }
+}
+
+/// Lets the provider be chosen at runtime (e.g. from command line options).
+impl<P: LogProvider + ?Sized> LogProvider for Box<P> {
+    fn start(&mut self) -> Result<()> {
+        (**self).start()
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        (**self).stop()
+    }
+
+    fn poll_logs(&mut self) -> Result<Vec<String>> {
+        (**self).poll_logs()
+    }
+
+    fn poll_raw_logs(&mut self) -> Result<Vec<RawLog>> {
+        (**self).poll_raw_logs()
+    }
+
+    fn is_finished(&self) -> bool {
+        (**self).is_finished()
+    }
+}
+
+/// What the provider thread does when the ring buffer to the UI is full.
+///
+/// The buffer fills up when a source bursts faster than the UI drains it
+/// (e.g. `adb logcat` dumping its backlog on connect).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// stop polling the provider until there is room again, nothing is lost
+    /// but the source may fall behind (pipes fill up and block the producer process)
+    Block,
+    /// keep the newest logs: the oldest queued logs are evicted to make room, so at
+    /// most as many logs as the ring buffer holds wait for the UI. Evicted logs still in
+    /// the ring buffer are skipped by [`OverflowState::drain`]
+    DropOldest,
+    /// keep what is already queued and drop incoming logs
+    #[default]
+    DropNewest,
+}
+
+/// Logs dropped by a provider thread, shared with the consumer of its ring buffer.
+///
+/// The provider thread cannot pop from the ring buffer, so with
+/// [`OverflowPolicy::DropOldest`] it only counts the oldest queued logs it evicts,
+/// and [`OverflowState::drain`] skips them on the consumer side.
+#[derive(Debug, Default)]
+pub struct OverflowState {
+    dropped: AtomicUsize,
+    // evicted logs at the front of the ring buffer, the lock keeps the count in step
+    // with what the consumer pops
+    evicted: Mutex<usize>,
+}
+
+impl OverflowState {
+    /// number of logs dropped because the ring buffer was full
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// reset the dropped counter, e.g. when the logs are cleared
+    pub fn reset_dropped(&self) {
+        self.dropped.store(0, Ordering::Relaxed);
+    }
+
+    /// pop every log waiting in the ring buffer, skipping evicted ones
+    pub fn drain(&self, consumer: &mut impl Consumer<Item = LogItem>) -> Vec<LogItem> {
+        let mut evicted = self.evicted.lock().unwrap_or_else(|e| e.into_inner());
+        consumer.skip(*evicted);
+        *evicted = 0;
+        consumer.pop_iter().collect()
+    }
+
+    /// make room for a new log by evicting the oldest queued one, in the ring buffer
+    /// if it still holds live logs, otherwise in the backlog
+    fn evict_oldest(
+        &self,
+        producer: &impl Producer<Item = LogItem>,
+        backlog: &mut VecDeque<LogItem>,
+    ) {
+        let mut evicted = self.evicted.lock().unwrap_or_else(|e| e.into_inner());
+        let queued = producer.occupied_len() - *evicted + backlog.len();
+        if queued <= producer.capacity().get() {
+            return;
+        }
+        if *evicted < producer.occupied_len() {
+            *evicted += 1;
+        } else {
+            backlog.pop_front();
+        }
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
 }
 
 /// Spawns a background thread that runs a provider and feeds logs into a ring buffer.
@@ -185,7 +332,7 @@ pub trait LogProvider: Send {
 /// This function is the glue between providers and the framework. It:
 /// 1. Starts the provider
 /// 2. Polls it at regular intervals
-/// 3. Parses raw strings into [`LogItem`]s
+/// 3. Parses raw logs into [`LogItem`]s and adds their provider metadata
 /// 4. Pushes items into the ring buffer for display
 ///
 /// # Parameters
@@ -203,7 +350,7 @@ pub trait LogProvider: Send {
 /// # Lifecycle
 ///
 /// 1. Calls `provider.start()`
-/// 2. Loops: `poll_logs()` → `parser.parse()` → push to ring buffer
+/// 2. Loops: `poll_raw_logs()` → `parser.parse()` → [`merge_sources`] → push to ring buffer
 /// 3. Sleeps for `poll_interval` between polls
 /// 4. On stop signal: calls `provider.stop()` and exits
 ///
@@ -252,17 +399,47 @@ pub trait LogProvider: Send {
 /// stop_signal.store(true, std::sync::atomic::Ordering::Relaxed);
 /// handle.join().ok();
 /// ```
+///
+/// Logs that don't fit into a full ring buffer are dropped, see
+/// [`spawn_provider_thread_with_policy`] to choose a different [`OverflowPolicy`].
 pub fn spawn_provider_thread<P>(
+    provider: P,
+    parser: Arc<dyn LogParser>,
+    producer: impl Producer<Item = LogItem> + Send + 'static,
+    poll_interval: Duration,
+) -> (thread::JoinHandle<()>, Arc<AtomicBool>)
+where
+    P: LogProvider + 'static,
+{
+    let (handle, should_stop, _overflow) = spawn_provider_thread_with_policy(
+        provider,
+        parser,
+        producer,
+        poll_interval,
+        OverflowPolicy::DropNewest,
+    );
+    (handle, should_stop)
+}
+
+/// Like [`spawn_provider_thread`], with an explicit [`OverflowPolicy`].
+///
+/// Additionally returns the [`OverflowState`] counting the logs dropped because the
+/// ring buffer was full. With [`OverflowPolicy::DropOldest`], read the ring buffer
+/// through [`OverflowState::drain`].
+pub fn spawn_provider_thread_with_policy<P>(
     mut provider: P,
     parser: Arc<dyn LogParser>,
     mut producer: impl Producer<Item = LogItem> + Send + 'static,
     poll_interval: Duration,
-) -> (thread::JoinHandle<()>, Arc<AtomicBool>)
+    policy: OverflowPolicy,
+) -> (thread::JoinHandle<()>, Arc<AtomicBool>, Arc<OverflowState>)
 where
     P: LogProvider + 'static,
 {
     let should_stop = Arc::new(AtomicBool::new(false));
     let should_stop_clone = should_stop.clone();
+    let overflow = Arc::new(OverflowState::default());
+    let overflow_clone = overflow.clone();
 
     let handle = thread::spawn(move || {
         if let Err(e) = provider.start() {
@@ -272,15 +449,38 @@ where
 
         log::debug!("Provider thread started");
 
+        // newest logs waiting for room in the ring buffer (DropOldest only)
+        let mut backlog = VecDeque::new();
+
         while !should_stop_clone.load(Ordering::Relaxed) {
-            match provider.poll_logs() {
+            push_backlog(&mut producer, &mut backlog);
+
+            match provider.poll_raw_logs() {
                 Ok(raw_logs) => {
-                    for raw_log in raw_logs {
-                        // parser may return None if it acts as a filter
-                        if let Some(log_item) = parser.parse(&raw_log)
-                            && producer.try_push(log_item).is_err()
-                        {
-                            log::debug!("Ring buffer full, dropping log");
+                    // parser may return None if it acts as a filter
+                    let log_items = merge_sources(
+                        raw_logs
+                            .iter()
+                            .filter_map(|raw| raw.parse(parser.as_ref()))
+                            .collect(),
+                    );
+                    for log_item in log_items {
+                        match policy {
+                            OverflowPolicy::Block => {
+                                if !push_blocking(&mut producer, log_item, &should_stop_clone) {
+                                    break;
+                                }
+                            }
+                            OverflowPolicy::DropOldest => {
+                                backlog.push_back(log_item);
+                                push_backlog(&mut producer, &mut backlog);
+                                overflow_clone.evict_oldest(&producer, &mut backlog);
+                            }
+                            OverflowPolicy::DropNewest => {
+                                if producer.try_push(log_item).is_err() {
+                                    overflow_clone.dropped.fetch_add(1, Ordering::Relaxed);
+                                }
+                            }
                         }
                     }
                 }
@@ -299,7 +499,37 @@ where
         log::debug!("Provider thread stopped");
     });
 
-    (handle, should_stop)
+    (handle, should_stop, overflow)
+}
+
+/// move waiting logs into the ring buffer while there is room
+fn push_backlog(producer: &mut impl Producer<Item = LogItem>, backlog: &mut VecDeque<LogItem>) {
+    while let Some(log_item) = backlog.pop_front() {
+        if let Err(log_item) = producer.try_push(log_item) {
+            backlog.push_front(log_item);
+            break;
+        }
+    }
+}
+
+/// wait until the log fits, returns false if the thread was asked to stop first
+fn push_blocking(
+    producer: &mut impl Producer<Item = LogItem>,
+    mut log_item: LogItem,
+    should_stop: &AtomicBool,
+) -> bool {
+    loop {
+        match producer.try_push(log_item) {
+            Ok(()) => return true,
+            Err(rejected) => {
+                if should_stop.load(Ordering::Relaxed) {
+                    return false;
+                }
+                log_item = rejected;
+                thread::sleep(BLOCKED_RETRY_INTERVAL);
+            }
+        }
+    }
 }
 
 fn sleep_interruptible(duration: Duration, should_stop: &AtomicBool) {
@@ -321,3 +551,92 @@ fn sleep_interruptible(duration: Duration, should_stop: &AtomicBool) {
         elapsed += sleep_time;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::{OnceProvider, wait_for};
+    use super::*;
+    use ringbuf::{HeapRb, traits::Split};
+
+    #[test]
+    fn test_drop_oldest_keeps_newest_logs() {
+        let (producer, mut consumer) = HeapRb::<LogItem>::new(2).split();
+        let (handle, stop, overflow) = spawn_provider_thread_with_policy(
+            OnceProvider::numbered(6),
+            Arc::new(PlainTextParser),
+            producer,
+            Duration::from_millis(1),
+            OverflowPolicy::DropOldest,
+        );
+
+        // 0 and 1 are evicted from the ring buffer, 2 and 3 from the backlog
+        wait_for(|| overflow.dropped() == 4);
+        let mut received: Vec<String> = Vec::new();
+        wait_for(|| {
+            received.extend(
+                overflow
+                    .drain(&mut consumer)
+                    .into_iter()
+                    .map(|item| item.content),
+            );
+            received.len() == 2
+        });
+        stop.store(true, Ordering::Relaxed);
+        handle.join().unwrap();
+
+        assert_eq!(received, vec!["4", "5"]);
+        assert_eq!(overflow.dropped(), 4);
+    }
+
+    #[test]
+    fn test_block_loses_nothing() {
+        let (producer, mut consumer) = HeapRb::<LogItem>::new(2).split();
+        let (handle, stop, overflow) = spawn_provider_thread_with_policy(
+            OnceProvider::numbered(6),
+            Arc::new(PlainTextParser),
+            producer,
+            Duration::from_millis(1),
+            OverflowPolicy::Block,
+        );
+
+        let mut received: Vec<String> = Vec::new();
+        wait_for(|| {
+            received.extend(consumer.pop_iter().map(|item| item.content));
+            received.len() == 6
+        });
+        stop.store(true, Ordering::Relaxed);
+        handle.join().unwrap();
+
+        assert_eq!(received, vec!["0", "1", "2", "3", "4", "5"]);
+        assert_eq!(overflow.dropped(), 0);
+    }
+
+    #[test]
+    fn test_raw_log_metadata_reaches_items() {
+        let (producer, mut consumer) = HeapRb::<LogItem>::new(4).split();
+        let (handle, stop) = spawn_provider_thread(
+            OnceProvider::new([
+                RawLog::new("0").with_metadata("file", "a.log"),
+                RawLog::new("1").with_metadata("file", "a.log"),
+            ]),
+            Arc::new(PlainTextParser),
+            producer,
+            Duration::from_millis(1),
+        );
+
+        let mut received: Vec<LogItem> = Vec::new();
+        wait_for(|| {
+            received.extend(consumer.pop_iter());
+            received.len() == 2
+        });
+        stop.store(true, Ordering::Relaxed);
+        handle.join().unwrap();
+
+        assert_eq!(received.len(), 2);
+        assert!(
+            received
+                .iter()
+                .all(|item| item.get_metadata("file") == Some("a.log"))
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use super::{LogDetailLevel, LogItem, LogParser, LogProvider, PlainTextParser, RawLog, Severity};
+use crate::theme::Theme;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use ratatui::style::Color;
+use std::{collections::VecDeque, sync::Arc};
+
+/// metadata key holding the name of the source a merged log came from
+pub const SOURCE_METADATA_KEY: &str = "source";
+
+struct Source {
+    name: String,
+    provider: Box<dyn LogProvider>,
+    parser: Arc<dyn LogParser>,
+}
+
+/// Runs several provider/parser pairs as one time-ordered stream.
+///
+/// Every log gets a `source` metadata entry with the name of its source, so it can
+/// be filtered with `source:<name>`, and the UI shows it as a tag in the color from
+/// [`MultiProvider::source_colors`]. Parse the stream with [`MultiProvider::parser`],
+/// which hands each log to the parser of its source.
+///
+/// A poll returns the logs of each source one after the other. Once parsed, the
+/// provider thread merges them with [`merge_sources`] by the timestamp their parser
+/// read, logs without one are ordered by arrival. The order within a source is
+/// always kept.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lazylog_framework::provider::{FileTailProvider, MultiProvider, PlainTextParser};
+/// use lazylog_framework::AppDesc;
+/// use std::sync::Arc;
+///
+/// let multi = MultiProvider::new()
+///     .source("app", FileTailProvider::new("app.log"), Arc::new(PlainTextParser))
+///     .source("db", FileTailProvider::new("db.log"), Arc::new(PlainTextParser));
+///
+/// let mut desc = AppDesc::new(multi.parser());
+/// desc.source_colors = multi.source_colors(&desc.theme);
+/// ```
+#[derive(Default)]
+pub struct MultiProvider {
+    sources: Vec<Source>,
+}
+
+impl MultiProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a source (builder pattern), `name` is what its logs are tagged with.
+    pub fn source(
+        mut self,
+        name: impl Into<String>,
+        provider: impl LogProvider + 'static,
+        parser: Arc<dyn LogParser>,
+    ) -> Self {
+        self.sources.push(Source {
+            name: name.into(),
+            provider: Box::new(provider),
+            parser,
+        });
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Parser dispatching each log to the parser of its source.
+    pub fn parser(&self) -> Arc<dyn LogParser> {
+        Arc::new(MultiParser {
+            sources: self
+                .sources
+                .iter()
+                .map(|source| (source.name.clone(), source.parser.clone()))
+                .collect(),
+        })
+    }
+
+    /// Tag color of every source from the palette of `theme`, for
+    /// [`AppDesc::source_colors`](crate::AppDesc::source_colors).
+    pub fn source_colors(&self, theme: &Theme) -> Vec<(String, Color)> {
+        self.sources
+            .iter()
+            .enumerate()
+            .map(|(index, source)| (source.name.clone(), theme.source_color(index)))
+            .collect()
+    }
+}
+
+impl LogProvider for MultiProvider {
+    fn start(&mut self) -> Result<()> {
+        for source in &mut self.sources {
+            source
+                .provider
+                .start()
+                .with_context(|| format!("failed to start source {}", source.name))?;
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        // stop every source even if one fails, report the first error
+        let mut result = Ok(());
+        for source in &mut self.sources {
+            let stopped = source
+                .provider
+                .stop()
+                .with_context(|| format!("failed to stop source {}", source.name));
+            result = result.and(stopped);
+        }
+        result
+    }
+
+    fn poll_logs(&mut self) -> Result<Vec<String>> {
+        Ok(self
+            .poll_raw_logs()?
+            .into_iter()
+            .map(|raw| raw.text)
+            .collect())
+    }
+
+    fn poll_raw_logs(&mut self) -> Result<Vec<RawLog>> {
+        let mut polled = Vec::new();
+        for source in &mut self.sources {
+            let raw_logs = match source.provider.poll_raw_logs() {
+                Ok(raw_logs) => raw_logs,
+                // a failing source must not hold back the others
+                Err(e) => {
+                    log::debug!("Provider poll error from {}: {}", source.name, e);
+                    Vec::new()
+                }
+            };
+            polled.extend(
+                raw_logs
+                    .into_iter()
+                    .map(|raw| raw.with_metadata(SOURCE_METADATA_KEY, source.name.as_str())),
+            );
+        }
+        Ok(polled)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.sources
+            .iter()
+            .all(|source| source.provider.is_finished())
+    }
+}
+
+/// logs of one source, named by its `source` metadata, with the time they merge by
+type SourceQueue = (Option<String>, VecDeque<(DateTime<Local>, LogItem)>);
+
+/// Orders the parsed logs of one [`MultiProvider`] poll by timestamp.
+///
+/// Logs are grouped by their `source` metadata, each group keeps its order, and the
+/// groups are merged by taking the earliest head every time (ties go to the source
+/// seen first). Logs without a timestamp count as arriving now. Logs of a single
+/// source are returned unchanged.
+pub fn merge_sources(items: Vec<LogItem>) -> Vec<LogItem> {
+    let first_source = items
+        .first()
+        .and_then(|item| item.get_metadata(SOURCE_METADATA_KEY));
+    if items
+        .iter()
+        .all(|item| item.get_metadata(SOURCE_METADATA_KEY) == first_source)
+    {
+        return items;
+    }
+
+    let arrival = Local::now();
+    let mut merged = Vec::with_capacity(items.len());
+    let mut queues: Vec<SourceQueue> = Vec::new();
+    for item in items {
+        let source = item.get_metadata(SOURCE_METADATA_KEY).map(str::to_string);
+        let time = item.timestamp.unwrap_or(arrival);
+        match queues.iter_mut().find(|(name, _)| *name == source) {
+            Some((_, queue)) => queue.push_back((time, item)),
+            None => queues.push((source, VecDeque::from([(time, item)]))),
+        }
+    }
+
+    while let Some((_, index)) = queues
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (_, queue))| queue.front().map(|(time, _)| (*time, index)))
+        .min()
+    {
+        if let Some((_, item)) = queues[index].1.pop_front() {
+            merged.push(item);
+        }
+    }
+    merged
+}
+
+/// Parser of a [`MultiProvider`] stream, see [`MultiProvider::parser`].
+///
+/// Logs are handed to the parser of the source named in their `source` metadata,
+/// logs without one go to the first source.
+pub struct MultiParser {
+    sources: Vec<(String, Arc<dyn LogParser>)>,
+}
+
+impl MultiParser {
+    fn parser_for(&self, source: Option<&str>) -> &dyn LogParser {
+        source
+            .and_then(|source| self.sources.iter().find(|(name, _)| name == source))
+            .or_else(|| self.sources.first())
+            .map_or(&PlainTextParser, |(_, parser)| parser.as_ref())
+    }
+
+    fn item_parser(&self, item: &LogItem) -> &dyn LogParser {
+        self.parser_for(item.get_metadata(SOURCE_METADATA_KEY))
+    }
+}
+
+impl LogParser for MultiParser {
+    fn parse(&self, raw_log: &str) -> Option<LogItem> {
+        self.parser_for(None).parse(raw_log)
+    }
+
+    fn parse_raw(&self, raw_log: &RawLog) -> Option<LogItem> {
+        let source = raw_log
+            .metadata
+            .iter()
+            .rev()
+            .find(|(key, _)| key == SOURCE_METADATA_KEY)
+            .map(|(_, name)| name.as_str());
+        self.parser_for(source).parse_raw(raw_log)
+    }
+
+    fn format_preview(&self, item: &LogItem, detail_level: LogDetailLevel) -> String {
+        self.item_parser(item).format_preview(item, detail_level)
+    }
+
+    fn get_searchable_text(&self, item: &LogItem, detail_level: LogDetailLevel) -> String {
+        self.item_parser(item)
+            .get_searchable_text(item, detail_level)
+    }
+
+    fn make_yank_content(&self, item: &LogItem) -> String {
+        self.item_parser(item).make_yank_content(item)
+    }
+
+    fn severity(&self, item: &LogItem) -> Option<Severity> {
+        self.item_parser(item).severity(item)
+    }
+
+    fn max_detail_level(&self) -> LogDetailLevel {
+        self.sources
+            .iter()
+            .map(|(_, parser)| parser.max_detail_level())
+            .max()
+            .unwrap_or_else(|| PlainTextParser.max_detail_level())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::test_support::OnceProvider;
+    use chrono::TimeZone;
+
+    /// reads a leading `<seconds>` as the timestamp, logs without one have none
+    struct SecondsParser;
+
+    impl LogParser for SecondsParser {
+        fn parse(&self, raw_log: &str) -> Option<LogItem> {
+            let item = LogItem::new(raw_log.to_string(), raw_log.to_string());
+            let seconds = raw_log.split(' ').next()?.parse::<i64>().ok();
+            Some(match seconds {
+                Some(seconds) => item.with_timestamp(Local.timestamp_opt(seconds, 0).unwrap()),
+                None => item,
+            })
+        }
+
+        fn format_preview(&self, item: &LogItem, _detail_level: LogDetailLevel) -> String {
+            format!("seconds: {}", item.content)
+        }
+
+        fn get_searchable_text(&self, item: &LogItem, _detail_level: LogDetailLevel) -> String {
+            item.content.clone()
+        }
+    }
+
+    #[test]
+    fn test_merges_by_timestamp_and_keeps_source_order() {
+        let mut multi = MultiProvider::new()
+            .source(
+                "a",
+                OnceProvider::new(["10 a1", "30 a2", "continued", "50 a3"]),
+                Arc::new(SecondsParser),
+            )
+            .source(
+                "b",
+                OnceProvider::new(["20 b1", "40 b2"]),
+                Arc::new(SecondsParser),
+            );
+
+        let parser = multi.parser();
+        let items: Vec<LogItem> = multi
+            .poll_raw_logs()
+            .unwrap()
+            .iter()
+            .filter_map(|raw| raw.parse(parser.as_ref()))
+            .collect();
+        let items = merge_sources(items);
+        let texts: Vec<&str> = items.iter().map(|item| item.content.as_str()).collect();
+        // "continued" has no timestamp, it arrived now and holds back the rest of "a"
+        assert_eq!(
+            texts,
+            vec!["10 a1", "20 b1", "30 a2", "40 b2", "continued", "50 a3"]
+        );
+        assert_eq!(items[1].get_metadata("source"), Some("b"));
+    }
+
+    #[test]
+    fn test_parser_dispatches_by_source() {
+        let mut multi = MultiProvider::new()
+            .source(
+                "plain",
+                OnceProvider::new(["1 x"]),
+                Arc::new(PlainTextParser),
+            )
+            .source("secs", OnceProvider::new(["2 y"]), Arc::new(SecondsParser));
+        let parser = multi.parser();
+
+        let items: Vec<LogItem> = multi
+            .poll_raw_logs()
+            .unwrap()
+            .iter()
+            .filter_map(|raw| raw.parse(parser.as_ref()))
+            .collect();
+        let items = merge_sources(items);
+
+        // the plain text has no timestamp, so it sorts after the timestamped log
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].get_metadata("source"), Some("secs"));
+        assert_eq!(parser.format_preview(&items[0], 0), "seconds: 2 y");
+        assert_eq!(items[1].get_metadata("source"), Some("plain"));
+        assert_eq!(parser.format_preview(&items[1], 0), "1 x");
+        assert_eq!(
+            multi.source_colors(&Theme::light()),
+            vec![
+                ("plain".to_string(), Color::Cyan),
+                ("secs".to_string(), Color::Magenta),
+            ]
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use super::{LogDetailLevel, LogItem, LogParser};
+
+/// Parser for unstructured text, every record becomes one log item as-is.
+///
+/// Detail level 0 shows the content only, level 1 prefixes the time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PlainTextParser;
+
+impl PlainTextParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LogParser for PlainTextParser {
+    fn parse(&self, raw_log: &str) -> Option<LogItem> {
+        Some(LogItem::new(raw_log.to_string(), raw_log.to_string()))
+    }
+
+    fn format_preview(&self, item: &LogItem, detail_level: LogDetailLevel) -> String {
+        let first_line = item.content.lines().next().unwrap_or("");
+        match detail_level {
+            0 => first_line.to_string(),
+            _ => format!("[{}] {}", item.time, first_line),
+        }
+    }
+
+    fn get_searchable_text(&self, item: &LogItem, _detail_level: LogDetailLevel) -> String {
+        item.content.clone()
+    }
+
+    fn make_yank_content(&self, item: &LogItem) -> String {
+        item.raw_content.clone()
+    }
+
+    fn max_detail_level(&self) -> LogDetailLevel {
+        1
+    }
+}
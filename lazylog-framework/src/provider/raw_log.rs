@@ -0,0 +1,56 @@
+use super::log_item::{LogItem, LogParser};
+
+/// A raw log plus metadata known to the provider rather than the text itself.
+///
+/// Returned by [`LogProvider::poll_raw_logs`](super::LogProvider::poll_raw_logs).
+/// Providers that follow several sources at once use it to tell which one a log
+/// came from; the metadata is added to the parsed [`LogItem`].
+///
+/// # Examples
+///
+/// ```rust
+/// use lazylog_framework::provider::RawLog;
+///
+/// let raw = RawLog::new("connected").with_metadata("file", "device-1.log");
+/// assert_eq!(raw.text, "connected");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawLog {
+    /// raw log text, handed to [`LogParser::parse`](super::LogParser::parse)
+    pub text: String,
+
+    /// extra metadata, applied after parsing (overrides keys set by the parser)
+    pub metadata: Vec<(String, String)>,
+}
+
+impl RawLog {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            metadata: Vec::new(),
+        }
+    }
+
+    /// Adds metadata to the raw log (builder pattern).
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+
+    /// Parses `text` with `parser` and adds the metadata to the item.
+    pub fn parse(&self, parser: &dyn LogParser) -> Option<LogItem> {
+        parser.parse_raw(self)
+    }
+}
+
+impl From<String> for RawLog {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&str> for RawLog {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
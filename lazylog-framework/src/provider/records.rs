@@ -0,0 +1,213 @@
+use anyhow::Result;
+use regex::Regex;
+use std::time::{Duration, Instant};
+
+/// how long a text source must stay quiet before a pending record is emitted
+pub(crate) const QUIET_FLUSH_DELAY: Duration = Duration::from_millis(200);
+
+/// How a line-oriented source is grouped into raw log records.
+///
+/// Many log formats span several lines per entry (a header followed by the message,
+/// stack traces, pretty-printed JSON). Providers that read plain text use this to
+/// hand each complete entry to [`LogParser::parse`](crate::LogParser::parse) at once.
+///
+/// # Examples
+///
+/// ```rust
+/// use lazylog_framework::provider::RecordSplit;
+///
+/// // one record per line
+/// let lines = RecordSplit::Lines;
+///
+/// // a record starts at every logcat `-v long` header, other lines are continuations
+/// let logcat = RecordSplit::start_pattern(r"^\[ \d{2}-\d{2} ").unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub enum RecordSplit {
+    /// every non-empty line is a record
+    #[default]
+    Lines,
+    /// a record starts at each line matching the pattern and runs until the next one
+    StartPattern(Regex),
+}
+
+impl RecordSplit {
+    /// Creates a [`RecordSplit::StartPattern`] from a regex string.
+    pub fn start_pattern(pattern: &str) -> Result<Self> {
+        Ok(Self::StartPattern(Regex::new(pattern)?))
+    }
+}
+
+/// Groups lines into records according to a [`RecordSplit`].
+///
+/// With a start pattern the last record stays pending until the next record starts,
+/// call [`RecordAssembler::flush`] once the source has been quiet for a while.
+#[derive(Debug, Default)]
+pub struct RecordAssembler {
+    split: RecordSplit,
+    pending: Vec<String>,
+}
+
+impl RecordAssembler {
+    pub fn new(split: RecordSplit) -> Self {
+        Self {
+            split,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Feeds one line (without the trailing newline), returns a record if one completed.
+    pub fn push_line(&mut self, line: &str) -> Option<String> {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        match &self.split {
+            RecordSplit::Lines => {
+                let line = line.trim_end();
+                (!line.trim_start().is_empty()).then(|| line.to_string())
+            }
+            RecordSplit::StartPattern(start) => {
+                let completed = if start.is_match(line) {
+                    self.flush()
+                } else {
+                    None
+                };
+                // lines before the first header form their own record
+                self.pending.push(line.to_string());
+                completed
+            }
+        }
+    }
+
+    /// Returns the pending record, if any.
+    pub fn flush(&mut self) -> Option<String> {
+        let record = self.pending.join("\n");
+        self.pending.clear();
+
+        let record = record.trim_end();
+        (!record.trim_start().is_empty()).then(|| record.to_string())
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+}
+
+/// Splits bytes read from a text source into lines and groups them with a [`RecordAssembler`].
+///
+/// A read can stop in the middle of a line (or of a multibyte character), so the bytes
+/// after the last newline are kept until the next read. The last record is held back
+/// until the next one starts or the source stays quiet, see [`RecordBuffer::flush_if_quiet`].
+#[derive(Debug)]
+pub struct RecordBuffer {
+    /// bytes after the last newline
+    partial_line: Vec<u8>,
+    assembler: RecordAssembler,
+    last_data: Instant,
+}
+
+impl RecordBuffer {
+    pub fn new(split: RecordSplit) -> Self {
+        Self {
+            partial_line: Vec::new(),
+            assembler: RecordAssembler::new(split),
+            last_data: Instant::now(),
+        }
+    }
+
+    /// Feeds newly read bytes, returns the records they completed.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        let mut records = Vec::new();
+        if bytes.is_empty() {
+            return records;
+        }
+        self.last_data = Instant::now();
+        self.partial_line.extend_from_slice(bytes);
+
+        let Some(last_newline) = self.partial_line.iter().rposition(|&b| b == b'\n') else {
+            return records;
+        };
+        let complete: Vec<u8> = self.partial_line.drain(..=last_newline).collect();
+
+        for line in complete[..last_newline].split(|&b| b == b'\n') {
+            records.extend(self.assembler.push_line(&String::from_utf8_lossy(line)));
+        }
+        records
+    }
+
+    /// Emits everything held back once no data arrived for a short while.
+    pub fn flush_if_quiet(&mut self) -> Vec<String> {
+        if self.has_pending() && self.last_data.elapsed() >= QUIET_FLUSH_DELAY {
+            self.flush()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Emits everything held back, including an unterminated last line.
+    pub fn flush(&mut self) -> Vec<String> {
+        let mut records = Vec::new();
+        if !self.partial_line.is_empty() {
+            let line = String::from_utf8_lossy(&self.partial_line).into_owned();
+            self.partial_line.clear();
+            records.extend(self.assembler.push_line(&line));
+        }
+        records.extend(self.assembler.flush());
+        records
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.partial_line.is_empty() || self.assembler.has_pending()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_skip_blank_lines() {
+        let mut assembler = RecordAssembler::new(RecordSplit::Lines);
+        assert_eq!(assembler.push_line("first\r"), Some("first".to_string()));
+        assert_eq!(assembler.push_line("   "), None);
+        assert!(!assembler.has_pending());
+    }
+
+    #[test]
+    fn test_start_pattern_groups_continuation_lines() {
+        let split = RecordSplit::start_pattern(r"^\[ \d{2}-\d{2} ").unwrap();
+        let mut assembler = RecordAssembler::new(split);
+
+        assert_eq!(
+            assembler.push_line("[ 11-14 15:48:35.135 1:2 E/Tag ]"),
+            None
+        );
+        assert_eq!(assembler.push_line("message line 1"), None);
+        assert_eq!(assembler.push_line(""), None);
+        assert_eq!(
+            assembler.push_line("[ 11-14 15:48:36.000 1:2 I/Tag ]"),
+            Some("[ 11-14 15:48:35.135 1:2 E/Tag ]\nmessage line 1".to_string())
+        );
+        assert_eq!(
+            assembler.flush(),
+            Some("[ 11-14 15:48:36.000 1:2 I/Tag ]".to_string())
+        );
+        assert_eq!(assembler.flush(), None);
+    }
+
+    #[test]
+    fn test_buffer_holds_back_partial_line_and_split_character() {
+        let mut buffer = RecordBuffer::new(RecordSplit::Lines);
+        let bytes = "first\ndétail\n".as_bytes();
+        // cut inside the two-byte `é`
+        let cut = "first\nd".len() + 1;
+
+        assert_eq!(buffer.push(&bytes[..cut]), vec!["first"]);
+        assert!(buffer.has_pending());
+        assert_eq!(buffer.push(&bytes[cut..]), vec!["détail"]);
+        assert!(!buffer.has_pending());
+
+        buffer.push(b"unterminated");
+        assert_eq!(buffer.flush(), vec!["unterminated"]);
+        assert!(!buffer.has_pending());
+    }
+}
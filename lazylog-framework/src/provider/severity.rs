@@ -0,0 +1,107 @@
+/// Normalized severity of a log item, ordered from least to most severe.
+///
+/// Sources spell levels differently (`E` in logcat, `<Error>` in syslog, `ERROR` in DYEH);
+/// [`Severity::from_level`] maps the common spellings so the UI can color and filter
+/// them uniformly. Parsers expose it through [`LogParser::severity`](crate::LogParser::severity).
+///
+/// # Examples
+///
+/// ```rust
+/// use lazylog_framework::provider::Severity;
+///
+/// assert_eq!(Severity::from_level("E"), Some(Severity::Error));
+/// assert_eq!(Severity::from_level("warning"), Some(Severity::Warning));
+/// assert!(Severity::Error > Severity::Info);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Verbose,
+    Debug,
+    Info,
+    /// noteworthy but not a problem (iOS `Notice`, DYEH `SYSTEM`)
+    Notice,
+    Warning,
+    Error,
+    /// crashes, assertions, iOS `Fault`
+    Fatal,
+}
+
+impl Severity {
+    /// every severity, least severe first
+    pub const ALL: [Self; 7] = [
+        Self::Verbose,
+        Self::Debug,
+        Self::Info,
+        Self::Notice,
+        Self::Warning,
+        Self::Error,
+        Self::Fatal,
+    ];
+
+    /// Maps a level string to a severity (case-insensitive), `None` for unknown levels.
+    ///
+    /// Recognizes logcat letters (`V D I W E F A`), syslog/OSLog names (`Notice`, `Fault`,
+    /// `Default`) and the usual words (`TRACE`, `WARN`, `CRITICAL`, ...).
+    pub fn from_level(level: &str) -> Option<Self> {
+        let level = level.trim().trim_start_matches('<').trim_end_matches('>');
+        let severity = match level.to_ascii_uppercase().as_str() {
+            "V" | "VERBOSE" | "TRACE" => Self::Verbose,
+            "D" | "DEBUG" => Self::Debug,
+            "I" | "INFO" | "DEFAULT" => Self::Info,
+            "N" | "NOTICE" | "SYSTEM" => Self::Notice,
+            "W" | "WARN" | "WARNING" => Self::Warning,
+            "E" | "ERROR" | "ERR" => Self::Error,
+            "F" | "A" | "FATAL" | "FAULT" | "CRITICAL" | "ASSERT" | "PANIC" => Self::Fatal,
+            _ => return None,
+        };
+        Some(severity)
+    }
+
+    /// single-letter form (`V D I N W E F`), accepted back by [`Severity::from_level`]
+    pub fn letter(self) -> char {
+        match self {
+            Self::Verbose => 'V',
+            Self::Debug => 'D',
+            Self::Info => 'I',
+            Self::Notice => 'N',
+            Self::Warning => 'W',
+            Self::Error => 'E',
+            Self::Fatal => 'F',
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Verbose => "verbose",
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Notice => "notice",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Fatal => "fatal",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_level_covers_source_spellings() {
+        // logcat
+        assert_eq!(Severity::from_level("V"), Some(Severity::Verbose));
+        assert_eq!(Severity::from_level("D"), Some(Severity::Debug));
+        assert_eq!(Severity::from_level("A"), Some(Severity::Fatal));
+        // syslog / OSLog
+        assert_eq!(Severity::from_level("<Error>"), Some(Severity::Error));
+        assert_eq!(Severity::from_level("Notice"), Some(Severity::Notice));
+        assert_eq!(Severity::from_level("Fault"), Some(Severity::Fatal));
+        // DYEH
+        assert_eq!(Severity::from_level("SYSTEM"), Some(Severity::Notice));
+        assert_eq!(Severity::from_level(" warn "), Some(Severity::Warning));
+
+        assert_eq!(Severity::from_level(""), None);
+        assert_eq!(Severity::from_level("effect"), None);
+    }
+}
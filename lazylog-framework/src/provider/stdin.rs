@@ -0,0 +1,179 @@
+use super::{
+    LogProvider,
+    records::{QUIET_FLUSH_DELAY, RecordAssembler, RecordSplit},
+};
+use anyhow::{Result, anyhow};
+use std::{
+    io::{self, BufRead, BufReader, Read},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+    time::Instant,
+};
+
+/// Reads logs from standard input (e.g. `some-command 2>&1 | lazylog --stdin`).
+///
+/// Reading blocks, so lines are read on a background thread and buffered until
+/// [`LogProvider::poll_logs`] drains them. The provider reports
+/// [`LogProvider::is_finished`] once the input is closed and everything was returned.
+///
+/// Lines are grouped into records with a [`RecordSplit`], one record per line by default.
+pub struct StdinProvider {
+    source: Option<Box<dyn Read + Send>>,
+    receiver: Option<Receiver<String>>,
+    assembler: RecordAssembler,
+    last_data: Instant,
+    closed: bool,
+}
+
+impl StdinProvider {
+    pub fn new() -> Self {
+        Self::from_reader(io::stdin())
+    }
+
+    /// Reads from any blocking reader instead of stdin (e.g. a named pipe or a socket).
+    pub fn from_reader(reader: impl Read + Send + 'static) -> Self {
+        Self {
+            source: Some(Box::new(reader)),
+            receiver: None,
+            assembler: RecordAssembler::new(RecordSplit::Lines),
+            last_data: Instant::now(),
+            closed: false,
+        }
+    }
+
+    /// How lines are grouped into records (default: [`RecordSplit::Lines`]).
+    pub fn record_split(mut self, split: RecordSplit) -> Self {
+        self.assembler = RecordAssembler::new(split);
+        self
+    }
+}
+
+impl Default for StdinProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogProvider for StdinProvider {
+    fn start(&mut self) -> Result<()> {
+        let source = self
+            .source
+            .take()
+            .ok_or_else(|| anyhow!("StdinProvider can only be started once"))?;
+        let (sender, receiver) = mpsc::channel();
+
+        // the thread is detached: a blocking read cannot be interrupted,
+        // it ends on its own when the input closes or the receiver is dropped
+        thread::Builder::new()
+            .name("lazylog-stdin".to_string())
+            .spawn(move || {
+                let mut reader = BufReader::new(source);
+                let mut buf = Vec::new();
+                loop {
+                    buf.clear();
+                    match reader.read_until(b'\n', &mut buf) {
+                        Ok(0) => break,
+                        Ok(_) => {
+                            let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+                            let line = String::from_utf8_lossy(line).into_owned();
+                            if sender.send(line).is_err() {
+                                break;
+                            }
+                        }
+                        Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                        Err(e) => {
+                            log::debug!("Stdin read error: {}", e);
+                            break;
+                        }
+                    }
+                }
+            })?;
+
+        self.receiver = Some(receiver);
+        self.last_data = Instant::now();
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        self.receiver = None;
+        Ok(())
+    }
+
+    fn poll_logs(&mut self) -> Result<Vec<String>> {
+        let mut records = Vec::new();
+        let Some(receiver) = &self.receiver else {
+            return Ok(records);
+        };
+
+        let mut received_any = false;
+        loop {
+            match receiver.try_recv() {
+                Ok(line) => {
+                    received_any = true;
+                    records.extend(self.assembler.push_line(&line));
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.closed = true;
+                    break;
+                }
+            }
+        }
+
+        if received_any {
+            self.last_data = Instant::now();
+        }
+        if self.closed || (!received_any && self.last_data.elapsed() >= QUIET_FLUSH_DELAY) {
+            records.extend(self.assembler.flush());
+        }
+
+        Ok(records)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.closed && !self.assembler.has_pending()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn poll_until_finished(provider: &mut StdinProvider) -> Vec<String> {
+        let mut logs = Vec::new();
+        for _ in 0..200 {
+            logs.extend(provider.poll_logs().unwrap());
+            if provider.is_finished() {
+                return logs;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        panic!("provider did not finish, got {:?}", logs);
+    }
+
+    #[test]
+    fn test_reads_lines_until_input_closes() {
+        let input: &[u8] = b"first\r\n\nsecond\nunterminated";
+        let mut provider = StdinProvider::from_reader(input);
+        provider.start().unwrap();
+
+        assert_eq!(
+            poll_until_finished(&mut provider),
+            vec!["first", "second", "unterminated"]
+        );
+    }
+
+    #[test]
+    fn test_groups_records_and_flushes_last_on_close() {
+        let input: &[u8] = b"[1] start\n  detail\n[2] next\n";
+        let mut provider = StdinProvider::from_reader(input)
+            .record_split(RecordSplit::start_pattern(r"^\[\d+\]").unwrap());
+        provider.start().unwrap();
+
+        assert_eq!(
+            poll_until_finished(&mut provider),
+            vec!["[1] start\n  detail", "[2] next"]
+        );
+    }
+}
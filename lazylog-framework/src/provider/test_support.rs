@@ -0,0 +1,64 @@
+//! Helpers shared by the provider and app tests.
+
+use super::{LogProvider, RawLog};
+use anyhow::Result;
+use std::{
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// returns all its logs on the first poll, nothing afterwards
+pub(crate) struct OnceProvider(Vec<RawLog>);
+
+impl OnceProvider {
+    /// texts or [`RawLog`]s with metadata
+    pub(crate) fn new<L: Into<RawLog>>(logs: impl IntoIterator<Item = L>) -> Self {
+        Self(logs.into_iter().map(Into::into).collect())
+    }
+
+    /// `count` logs numbered from `0`
+    pub(crate) fn numbered(count: usize) -> Self {
+        Self::new((0..count).map(|i| i.to_string()))
+    }
+}
+
+impl LogProvider for OnceProvider {
+    fn start(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn poll_logs(&mut self) -> Result<Vec<String>> {
+        Ok(self
+            .poll_raw_logs()?
+            .into_iter()
+            .map(|raw_log| raw_log.text)
+            .collect())
+    }
+
+    fn poll_raw_logs(&mut self) -> Result<Vec<RawLog>> {
+        Ok(std::mem::take(&mut self.0))
+    }
+}
+
+/// poll `condition` until it holds, giving up after two seconds
+pub(crate) fn wait_for(mut condition: impl FnMut() -> bool) {
+    let start = Instant::now();
+    while !condition() && start.elapsed() < Duration::from_secs(2) {
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// a unique path in the temp directory, the file is not created
+pub(crate) fn temp_path(name: &str, extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "lazylog-{}-{}.{}",
+        name,
+        uuid::Uuid::new_v4(),
+        extension
+    ))
+}
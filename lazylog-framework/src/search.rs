@@ -0,0 +1,237 @@
+use crate::filter::{FilterMode, FilterQuery};
+use crate::provider::{LogDetailLevel, LogItem, LogParser};
+use anyhow::Result;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// search-and-jump over the displayed logs, unlike the filter it hides nothing
+///
+/// matches are kept as raw indices so they survive selection changes, and are
+/// extended incrementally as new logs are displayed
+pub struct SearchEngine {
+    /// the current query, `None` when no search is active
+    query: Option<FilterQuery>,
+    /// raw indices of displayed logs matching the query, ascending
+    matches: Vec<usize>,
+    /// displayed logs with a raw index below this have been searched
+    ///
+    /// follows the displayed logs rather than the raw ones: the filter only appends
+    /// rows past the last displayed one, but the before-context of a new match can
+    /// be older than the previous raw log count
+    searched_until: usize,
+    /// parser for converting log items to searchable text
+    parser: Option<Arc<dyn LogParser>>,
+}
+
+impl SearchEngine {
+    pub fn new() -> Self {
+        Self {
+            query: None,
+            matches: Vec::new(),
+            searched_until: 0,
+            parser: None,
+        }
+    }
+
+    /// set the parser to use for searching
+    pub fn set_formatter(&mut self, parser: Arc<dyn LogParser>) {
+        self.parser = Some(parser);
+    }
+
+    pub fn query(&self) -> Option<&FilterQuery> {
+        self.query.as_ref()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.query.is_some()
+    }
+
+    /// raw indices of the matching displayed logs, ascending
+    pub fn matches(&self) -> &[usize] {
+        &self.matches
+    }
+
+    /// replace the query, an empty query ends the search
+    ///
+    /// returns an error if the query cannot be parsed, the previous query is kept;
+    /// call `refresh` afterwards to recompute the matches
+    pub fn set_query(&mut self, query: &str, mode: FilterMode) -> Result<()> {
+        self.query = if query.is_empty() {
+            None
+        } else {
+            Some(FilterQuery::parse(query, mode)?)
+        };
+        self.matches.clear();
+        self.searched_until = 0;
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.query = None;
+        self.matches.clear();
+        self.searched_until = 0;
+    }
+
+    /// search all displayed logs again (after the query, filter or detail level changed)
+    pub fn refresh(
+        &mut self,
+        raw_logs: &[LogItem],
+        displayed: &[usize],
+        detail_level: LogDetailLevel,
+    ) {
+        self.matches.clear();
+        self.searched_until = 0;
+        self.update(raw_logs, displayed, detail_level);
+    }
+
+    /// search only the displayed logs that arrived since the last call
+    pub fn update(
+        &mut self,
+        raw_logs: &[LogItem],
+        displayed: &[usize],
+        detail_level: LogDetailLevel,
+    ) {
+        let searched_until = displayed.last().map_or(self.searched_until, |&idx| idx + 1);
+        let (Some(query), Some(parser)) = (&self.query, &self.parser) else {
+            self.searched_until = searched_until;
+            return;
+        };
+
+        let start = displayed.partition_point(|&idx| idx < self.searched_until);
+        let new_displayed = &displayed[start..];
+        let is_match =
+            |&&idx: &&usize| query.matches(parser.as_ref(), &raw_logs[idx], detail_level);
+
+        // use parallel matching for large search spaces
+        if new_displayed.len() > 1000 {
+            let found: Vec<usize> = new_displayed.par_iter().filter(is_match).copied().collect();
+            self.matches.extend(found);
+        } else {
+            self.matches
+                .extend(new_displayed.iter().filter(is_match).copied());
+        }
+        self.searched_until = searched_until;
+    }
+
+    /// drop the first `count` raw logs from the matches, shifting the rest
+    ///
+    /// call this after removing the oldest items from the raw log buffer
+    pub fn evict_front(&mut self, count: usize) {
+        if count == 0 {
+            return;
+        }
+
+        self.matches.retain(|&idx| idx >= count);
+        for idx in &mut self.matches {
+            *idx -= count;
+        }
+        self.searched_until = self.searched_until.saturating_sub(count);
+    }
+
+    /// 0-based position of `raw_idx` among the matches
+    pub fn position(&self, raw_idx: usize) -> Option<usize> {
+        self.matches.binary_search(&raw_idx).ok()
+    }
+
+    /// the match after (or before) `from`, wrapping around at the ends like vim
+    ///
+    /// returns the raw index of the match and whether the search wrapped
+    pub fn next_match(&self, from: Option<usize>, forward: bool) -> Option<(usize, bool)> {
+        let first = *self.matches.first()?;
+        let last = *self.matches.last()?;
+
+        let Some(from) = from else {
+            return Some(if forward {
+                (first, false)
+            } else {
+                (last, false)
+            });
+        };
+
+        if forward {
+            let next = self.matches.partition_point(|&idx| idx <= from);
+            Some(match self.matches.get(next) {
+                Some(&idx) => (idx, false),
+                None => (first, true),
+            })
+        } else {
+            let prev = self.matches.partition_point(|&idx| idx < from);
+            Some(match prev.checked_sub(1) {
+                Some(prev) => (self.matches[prev], false),
+                None => (last, true),
+            })
+        }
+    }
+}
+
+impl Default for SearchEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::PlainTextParser;
+
+    fn logs(lines: &[&str]) -> Vec<LogItem> {
+        lines
+            .iter()
+            .map(|line| LogItem::new(line.to_string(), line.to_string()))
+            .collect()
+    }
+
+    fn engine(query: &str) -> SearchEngine {
+        let mut engine = SearchEngine::new();
+        engine.set_formatter(Arc::new(PlainTextParser));
+        engine.set_query(query, FilterMode::Literal).unwrap();
+        engine
+    }
+
+    #[test]
+    fn test_matches_only_displayed_logs_and_extends_incrementally() {
+        let mut raw = logs(&["error a", "info", "error b", "error c"]);
+        let mut engine = engine("error");
+
+        // "error b" is hidden by the filter
+        engine.refresh(&raw, &[0, 1, 3], 0);
+        assert_eq!(engine.matches(), &[0, 3]);
+
+        raw.extend(logs(&["error d", "debug"]));
+        engine.update(&raw, &[0, 1, 3, 4, 5], 0);
+        assert_eq!(engine.matches(), &[0, 3, 4]);
+        assert_eq!(engine.position(3), Some(1));
+
+        engine.evict_front(1);
+        assert_eq!(engine.matches(), &[2, 3]);
+    }
+
+    #[test]
+    fn test_update_searches_context_rows_of_new_matches() {
+        let mut raw = logs(&["crash", "error a", "ok"]);
+        let mut engine = engine("error");
+
+        // only the match is displayed at first
+        engine.refresh(&raw, &[0], 0);
+        assert!(engine.matches().is_empty());
+
+        // a new match brings in its before-context, older than the new logs
+        raw.extend(logs(&["crash"]));
+        engine.update(&raw, &[0, 1, 2, 3], 0);
+        assert_eq!(engine.matches(), &[1]);
+    }
+
+    #[test]
+    fn test_next_match_wraps_around() {
+        let raw = logs(&["error a", "info", "error b", "info"]);
+        let mut engine = engine("error");
+        engine.refresh(&raw, &[0, 1, 2, 3], 0);
+
+        assert_eq!(engine.next_match(None, true), Some((0, false)));
+        assert_eq!(engine.next_match(Some(0), true), Some((2, false)));
+        assert_eq!(engine.next_match(Some(3), true), Some((0, true)));
+        assert_eq!(engine.next_match(Some(1), false), Some((0, false)));
+        assert_eq!(engine.next_match(Some(0), false), Some((2, true)));
+    }
+}
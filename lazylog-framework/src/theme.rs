@@ -1,3 +1,4 @@
+use crate::provider::Severity;
 use ratatui::{prelude::*, style::Color};
 
 // basic 16 colors supported by macOS default terminal
@@ -30,37 +31,291 @@ pub const MODE_COLORS: &[(Option<&str>, Color)] = &[
     (None, Color::Gray), // default
 ];
 
-pub fn get_mode_color(mode_name: &Option<String>) -> Color {
-    if let Some(name) = mode_name {
-        for (mode, color) in MODE_COLORS {
-            if let Some(m) = mode
-                && name.to_lowercase().contains(m)
-            {
-                return *color;
-            }
+/// darker mode colors that stay readable on a light background
+pub const LIGHT_MODE_COLORS: &[(Option<&str>, Color)] = &[
+    (Some("ios"), Color::Blue),
+    (Some("ios effect"), Color::Blue),
+    (Some("android"), Color::Rgb(205, 100, 0)), // dark orange
+    (Some("android effect"), Color::Rgb(205, 100, 0)),
+    (Some("dyeh"), Color::Green),
+    (None, Color::DarkGray), // default
+];
+
+/// colors of the source tags when several providers are merged, in source order
+pub const SOURCE_COLORS: &[Color] = &[
+    Color::LightCyan,
+    Color::LightMagenta,
+    Color::Rgb(255, 165, 0), // orange
+    Color::LightBlue,
+    Color::LightGreen,
+    Color::LightYellow,
+];
+
+/// darker source tag colors that stay readable on a light background
+pub const LIGHT_SOURCE_COLORS: &[Color] = &[
+    Color::Cyan,
+    Color::Magenta,
+    Color::Rgb(205, 100, 0), // dark orange
+    Color::Blue,
+    Color::Green,
+    Color::Rgb(160, 100, 0), // dark yellow
+];
+
+/// colors and styles of every rendered element, chosen at startup through `AppDesc::theme`
+///
+/// ```
+/// use lazylog_framework::Theme;
+///
+/// let theme = Theme::from_name("light").unwrap();
+/// assert_eq!(theme.name, "light");
+/// assert!(Theme::from_name("solarized").is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: &'static str,
+    /// mode colors matched against the lower-cased mode name, `None` is the fallback
+    pub mode_colors: &'static [(Option<&'static str>, Color)],
+    /// tag colors of merged sources, in source order, repeated when there are more sources
+    pub source_colors: &'static [Color],
+    /// plain text of the logs, details and help panes
+    pub text: Color,
+    /// border of the focused block, `None` brightens the mode color
+    pub focused_border: Option<Color>,
+    pub scrollbar_thumb: Color,
+    pub focused_scrollbar_thumb: Color,
+    /// patched onto the cursor row and the visual selection
+    pub selected: Style,
+    /// patched onto rows shown only as context of a filter match
+    pub context: Style,
+    /// patched onto filter and search matches
+    pub highlight: Style,
+    pub info: Style,
+    pub warn: Style,
+    pub error: Style,
+    pub debug: Style,
+    pub verbose: Style,
+    pub status_bar: Style,
+    /// patched onto the status bar while the filter or search input is focused
+    pub filter_focus: Style,
+    /// default style of footer display events
+    pub display_event: Style,
+    /// foreground of a display event while it fades in or out
+    pub display_event_fade: Color,
+    /// export prompt in the footer
+    pub prompt: Style,
+    /// invalid filter or search pattern in the footer
+    pub status_error: Style,
+    /// text of the active tab, drawn on the mode color
+    pub active_tab: Style,
+    /// JSON payloads pretty-printed in the details pane
+    pub json_key: Style,
+    pub json_string: Style,
+    pub json_number: Style,
+    /// `true`, `false` and `null`
+    pub json_literal: Style,
+}
+
+impl Theme {
+    /// names accepted by [`Theme::from_name`]
+    pub const NAMES: [&'static str; 3] = ["dark", "light", "high-contrast"];
+
+    /// the default theme, for dark terminal backgrounds
+    pub fn dark() -> Self {
+        Self {
+            name: "dark",
+            mode_colors: MODE_COLORS,
+            source_colors: SOURCE_COLORS,
+            text: Color::Gray,
+            focused_border: None,
+            scrollbar_thumb: Color::Gray,
+            focused_scrollbar_thumb: Color::White,
+            selected: Style::new().bg(Color::DarkGray),
+            context: Style::new().add_modifier(Modifier::DIM),
+            highlight: Style::new().add_modifier(Modifier::BOLD.union(Modifier::UNDERLINED)),
+            info: Style::new().fg(Color::White),
+            warn: Style::new().fg(Color::LightYellow),
+            error: Style::new().fg(Color::LightRed),
+            debug: Style::new().fg(Color::LightGreen),
+            verbose: Style::new().fg(Color::DarkGray),
+            status_bar: Style::new(),
+            filter_focus: Style::new().bg(Color::DarkGray),
+            display_event: Style::new()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            display_event_fade: Color::Gray,
+            prompt: Style::new().fg(Color::Black).bg(Color::Cyan),
+            status_error: Style::new().fg(Color::White).bg(Color::Red),
+            active_tab: Style::new().fg(Color::Black).add_modifier(Modifier::BOLD),
+            json_key: Style::new().fg(Color::LightBlue),
+            json_string: Style::new().fg(Color::LightGreen),
+            json_number: Style::new().fg(Color::LightMagenta),
+            json_literal: Style::new().fg(Color::LightCyan),
         }
     }
-    Color::Gray
-}
 
-pub const TEXT_FG_COLOR: Color = Color::Gray;
+    /// for light terminal backgrounds
+    pub fn light() -> Self {
+        Self {
+            name: "light",
+            mode_colors: LIGHT_MODE_COLORS,
+            source_colors: LIGHT_SOURCE_COLORS,
+            text: Color::Black,
+            focused_border: None,
+            scrollbar_thumb: Color::DarkGray,
+            focused_scrollbar_thumb: Color::Black,
+            selected: Style::new().bg(Color::Gray),
+            context: Style::new().add_modifier(Modifier::DIM),
+            highlight: Style::new().add_modifier(Modifier::BOLD.union(Modifier::UNDERLINED)),
+            info: Style::new().fg(Color::Black),
+            warn: Style::new().fg(Color::Rgb(160, 100, 0)),
+            error: Style::new().fg(Color::Red),
+            debug: Style::new().fg(Color::Green),
+            verbose: Style::new().fg(Color::DarkGray),
+            status_bar: Style::new().fg(Color::Black),
+            filter_focus: Style::new().bg(Color::Gray),
+            display_event: Style::new()
+                .fg(Color::White)
+                .bg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+            display_event_fade: Color::Gray,
+            prompt: Style::new().fg(Color::White).bg(Color::Magenta),
+            status_error: Style::new().fg(Color::White).bg(Color::Red),
+            active_tab: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+            json_key: Style::new().fg(Color::Blue),
+            json_string: Style::new().fg(Color::Green),
+            json_number: Style::new().fg(Color::Magenta),
+            json_literal: Style::new().fg(Color::Cyan),
+        }
+    }
 
-#[allow(dead_code)]
-pub const LOG_HEADER_STYLE: Style = Style::new().fg(Color::White).bg(Color::DarkGray);
+    /// pure black and white with bright accents, the focused block gets a white border
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast",
+            mode_colors: MODE_COLORS,
+            source_colors: SOURCE_COLORS,
+            text: Color::White,
+            focused_border: Some(Color::White),
+            scrollbar_thumb: Color::Gray,
+            focused_scrollbar_thumb: Color::White,
+            selected: Style::new()
+                .fg(Color::Black)
+                .bg(Color::White)
+                .add_modifier(Modifier::BOLD),
+            context: Style::new().fg(Color::Gray),
+            highlight: Style::new()
+                .fg(Color::Black)
+                .bg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            info: Style::new().fg(Color::White),
+            warn: Style::new().fg(Color::LightYellow),
+            error: Style::new()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+            debug: Style::new().fg(Color::LightCyan),
+            verbose: Style::new().fg(Color::Gray),
+            status_bar: Style::new().fg(Color::White).bg(Color::Black),
+            filter_focus: Style::new().fg(Color::Black).bg(Color::White),
+            display_event: Style::new()
+                .fg(Color::Black)
+                .bg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            display_event_fade: Color::DarkGray,
+            prompt: Style::new().fg(Color::Black).bg(Color::LightCyan),
+            status_error: Style::new()
+                .fg(Color::White)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+            active_tab: Style::new().fg(Color::Black).add_modifier(Modifier::BOLD),
+            json_key: Style::new()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+            json_string: Style::new().fg(Color::LightGreen),
+            json_number: Style::new().fg(Color::LightYellow),
+            json_literal: Style::new().fg(Color::LightMagenta),
+        }
+    }
 
-pub const SELECTED_STYLE: Style = Style::new().bg(Color::DarkGray);
+    /// one of the bundled themes by name, see [`Theme::NAMES`]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
 
-pub const INFO_STYLE: Style = Style::new().fg(Color::White);
+    /// border color for a mode name, the first entry contained in the name wins
+    pub fn mode_color(&self, mode_name: &Option<String>) -> Color {
+        let fallback = self
+            .mode_colors
+            .iter()
+            .find_map(|(mode, color)| mode.is_none().then_some(*color))
+            .unwrap_or(self.text);
+        let Some(name) = mode_name else {
+            return fallback;
+        };
+        let name = name.to_lowercase();
+        self.mode_colors
+            .iter()
+            .find_map(|(mode, color)| mode.filter(|m| name.contains(m)).map(|_| *color))
+            .unwrap_or(fallback)
+    }
 
-pub const WARN_STYLE: Style = Style::new().fg(Color::LightYellow);
+    /// tag color of the `index`-th merged source
+    pub fn source_color(&self, index: usize) -> Color {
+        self.source_colors
+            .get(index % self.source_colors.len().max(1))
+            .copied()
+            .unwrap_or(self.text)
+    }
 
-pub const ERROR_STYLE: Style = Style::new().fg(Color::LightRed);
+    /// row style for a log item of the given severity
+    pub fn severity_style(&self, severity: Option<Severity>) -> Style {
+        match severity {
+            Some(Severity::Fatal) => self.error.add_modifier(Modifier::BOLD),
+            Some(Severity::Error) => self.error,
+            Some(Severity::Warning) => self.warn,
+            Some(Severity::Notice) => self.info,
+            Some(Severity::Debug) => self.debug,
+            Some(Severity::Verbose) => self.verbose,
+            Some(Severity::Info) | None => Style::new().fg(self.text),
+        }
+    }
+}
 
-pub const DEBUG_STYLE: Style = Style::new().fg(Color::LightGreen);
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
 
-pub const DISPLAY_EVENT_STYLE: Style = Style::new()
-    .fg(Color::Black)
-    .bg(Color::Yellow)
-    .add_modifier(Modifier::BOLD);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-pub const FILTER_FOCUS_STYLE: Style = Style::new().bg(Color::DarkGray);
+    #[test]
+    fn test_mode_color_matches_name_and_falls_back() {
+        let dark = Theme::dark();
+        assert_eq!(
+            dark.mode_color(&Some("Android Effect".to_string())),
+            Color::Rgb(255, 165, 0)
+        );
+        assert_eq!(dark.mode_color(&Some("file".to_string())), Color::Gray);
+        assert_eq!(dark.mode_color(&None), Color::Gray);
+
+        let light = Theme::light();
+        assert_eq!(light.mode_color(&Some("iOS".to_string())), Color::Blue);
+        assert_eq!(light.mode_color(&None), Color::DarkGray);
+    }
+
+    #[test]
+    fn test_bundled_themes_resolve_by_name() {
+        for name in Theme::NAMES {
+            assert_eq!(Theme::from_name(name).map(|theme| theme.name), Some(name));
+        }
+        assert_eq!(Theme::default().name, "dark");
+    }
+}
@@ -1,5 +1,6 @@
+use chrono::{DateTime, Local, Month, NaiveTime};
 use lazy_static::lazy_static;
-use lazylog_framework::provider::{LogDetailLevel, LogItem, LogParser};
+use lazylog_framework::provider::{LogDetailLevel, LogItem, LogParser, resolve_yearless_timestamp};
 use lazylog_parser::process_delta;
 use regex::Regex;
 
@@ -29,6 +30,17 @@ impl IosFullParser {
         }
         content.to_string()
     }
+
+    /// parse the leading `Oct 29 11:27:36[.335062]` syslog timestamp,
+    /// the missing year is resolved against `reference`
+    fn syslog_timestamp(raw_log: &str, reference: DateTime<Local>) -> Option<DateTime<Local>> {
+        let mut fields = raw_log.split_whitespace();
+        let month = fields.next()?.parse::<Month>().ok()?;
+        let day = fields.next()?.parse().ok()?;
+        let time = NaiveTime::parse_from_str(fields.next()?, "%H:%M:%S%.f").ok()?;
+
+        resolve_yearless_timestamp(month.number_from_month(), day, time, reference)
+    }
 }
 
 impl Default for IosFullParser {
@@ -70,8 +82,11 @@ impl LogParser for IosFullParser {
         };
 
         let mut item = LogItem::new(content, raw_log.to_string());
+        if let Some(timestamp) = Self::syslog_timestamp(raw_log, Local::now()) {
+            item = item.with_timestamp(timestamp);
+        }
         if !level.is_empty() {
-            item = item.with_metadata("level", level);
+            item = item.with_level(level);
         }
         if !tag.is_empty() {
             item = item.with_metadata("tag", tag);
@@ -192,7 +207,15 @@ impl LogParser for IosEffectParser {
             let log_items = process_delta(inner_content);
 
             // return first parsed item if available
-            if let Some(item) = log_items.into_iter().next() {
+            if let Some(mut item) = log_items.into_iter().next() {
+                // prefer the syslog timestamp when it is more precise than the
+                // structured `## date` (seconds only), which pins down the year
+                let reference = item.timestamp.unwrap_or_else(Local::now);
+                if let Some(timestamp) = IosFullParser::syslog_timestamp(raw_log, reference)
+                    && timestamp.timestamp_subsec_nanos() != 0
+                {
+                    item = item.with_timestamp(timestamp);
+                }
                 return Some(item);
             }
         }
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
 use lazy_static::lazy_static;
 use lazylog_framework::provider::LogItem;
 use regex::Regex;
@@ -64,10 +65,21 @@ fn split_header(line: &str) -> (String, String, String, String) {
     }
 }
 
+/// parse a full local timestamp such as `2025-11-14 15:48:35` with the given format
+pub fn parse_local_timestamp(s: &str, format: &str) -> Option<DateTime<Local>> {
+    let naive = NaiveDateTime::parse_from_str(s.trim(), format).ok()?;
+    Local.from_local_datetime(&naive).earliest()
+}
+
 fn parse_structured(block: &str) -> Option<LogItem> {
     ITEM_PARSE_RE.captures(block).map(|caps| {
         let raw_content = caps.get(2).map_or("", |m| m.as_str()).trim().to_string();
-        LogItem::new(raw_content.clone(), raw_content)
+        let item = LogItem::new(raw_content.clone(), raw_content);
+
+        match parse_local_timestamp(&caps[1], "%Y-%m-%d %H:%M:%S") {
+            Some(timestamp) => item.with_timestamp(timestamp),
+            None => item,
+        }
     })
 }
 
@@ -93,10 +105,13 @@ pub fn process_delta(delta: &str) -> Vec<LogItem> {
         {
             let (origin, level, tag, msg) = split_header(&it.content);
             let mut updated_item = LogItem::new(msg, it.raw_content);
+            if let Some(timestamp) = it.timestamp {
+                updated_item = updated_item.with_timestamp(timestamp);
+            }
 
             // add metadata if fields are not empty
             if !level.is_empty() {
-                updated_item = updated_item.with_metadata("level", level);
+                updated_item = updated_item.with_level(level);
             }
             if !origin.is_empty() {
                 updated_item = updated_item.with_metadata("origin", origin);